@@ -1,4 +1,4 @@
-use bench_suite_collect_results::BenchSuiteCollect;
+use bench_suite_collect_results::{BenchSuiteCollect, BenchSuiteDerive};
 use bench_suite_collector_config::BenchSuiteCollectConfig;
 use bench_suite_collector_dacapo_iteration::BenchSuiteCollectDacapoIteration;
 use bench_suite_collector_dacapo_latency::BenchSuiteCollectDacapoLatency;
@@ -15,6 +15,9 @@ use bench_suite_collector_zgc_gc_summary::BenchSuiteCollectZgcGcSummary;
 use bench_suite_collector_zgc_phases::BenchSuiteCollectZgcPhases;
 use bench_suite_collector_zgc_stats::BenchSuiteCollectZgcStats;
 use bench_suite_collector_zgc_task::BenchSuiteCollectZgcTask;
+use bench_suite_derive_threadstat_threads::BenchSuiteDeriveThreadstatThreads;
+use bench_suite_derive_zgc_phase_workers::BenchSuiteDeriveZgcPhaseWorkers;
+use bench_suite_types::BenchmarkKind;
 
 type Result<T> = std::result::Result<T, InvalidBenchmark>;
 
@@ -91,17 +94,31 @@ const SPECJBB_CONFIG: [fn() -> Box<dyn BenchSuiteCollect>; 13] = [
     BenchSuiteCollectSpecjbb::boxed,
 ];
 
+const DERIVE_CONFIG: [fn() -> Box<dyn BenchSuiteDerive>; 2] = [
+    BenchSuiteDeriveThreadstatThreads::boxed,
+    BenchSuiteDeriveZgcPhaseWorkers::boxed,
+];
+
 type CreateCollectorFunction = fn() -> Box<dyn BenchSuiteCollect>;
 
-/// Get the Collect Config for a benchmark name
+/// Get the Collect Config for a benchmark
 ///
 /// # Errors
-/// Returns `Err` if benchmark name does not have config
-pub fn get_collect_config(bench: &str) -> Result<&'static [CreateCollectorFunction]> {
+/// Returns `Err` if the benchmark does not have config
+pub fn get_collect_config(bench: &BenchmarkKind) -> Result<&'static [CreateCollectorFunction]> {
     Ok(match bench {
-        "dacapo_samples2" => &DACAPO_SAMPLES2_CONFIG,
-        "mark_abuse" => &MARK_ABUSE_CONFIG,
-        "specjbb" => &SPECJBB_CONFIG,
-        _ => return Err(InvalidBenchmark::new(bench.to_string())),
+        BenchmarkKind::DacapoSamples2 => &DACAPO_SAMPLES2_CONFIG,
+        BenchmarkKind::MarkAbuse => &MARK_ABUSE_CONFIG,
+        BenchmarkKind::Specjbb => &SPECJBB_CONFIG,
+        BenchmarkKind::Other(name) => return Err(InvalidBenchmark::new(name.to_string())),
     })
 }
+
+type CreateDeriveFunction = fn() -> Box<dyn BenchSuiteDerive>;
+
+/// Get the derivations to run over every benchmark's collected tables. Derivations whose
+/// dependencies were not collected for a run are skipped.
+#[must_use]
+pub fn get_derive_config() -> &'static [CreateDeriveFunction] {
+    &DERIVE_CONFIG
+}
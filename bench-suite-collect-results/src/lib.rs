@@ -1,10 +1,14 @@
 use anyhow::{Context, Result};
 use bench_suite_types::BenchSuiteRun;
 use lazy_read::{self, LazyRead};
-use polars::prelude::LazyFrame;
+use polars::prelude::{DataFrame, IntoLazy, LazyFrame, Schema, col};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::io::Read;
+use std::sync::Arc;
 
 use string_intern::Intern;
+use unified_log::LogLine;
 
 pub trait FileInfoInterface {
     fn name(&self) -> &str;
@@ -22,6 +26,15 @@ pub trait FileInfoInterface {
     ///
     /// Returns `Err` if reading the file fails.
     fn content_bytes(&mut self) -> Result<&[u8]>;
+
+    /// Calls `f` with every line of the file, without its line ending. Files that were not read
+    /// whole yet are streamed, after which their contents can no longer be read.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error `f` returns, or `Err` if reading the file fails, the contents are
+    /// not valid UTF-8, or the file was already streamed.
+    fn for_each_line(&mut self, f: &mut dyn FnMut(&str) -> Result<()>) -> Result<()>;
 }
 
 pub struct FileInfo<'a, T>
@@ -49,6 +62,9 @@ where
             .get_bytes()
             .context("Failed to read the files contents")
     }
+    fn for_each_line(&mut self, f: &mut dyn FnMut(&str) -> Result<()>) -> Result<()> {
+        self.content.for_each_line(f)
+    }
 }
 
 impl<'a, T> FileInfo<'a, T>
@@ -67,16 +83,101 @@ where
 }
 
 pub trait BenchSuiteCollect {
-    /// Processes a single file from a benchmark run's archive.
+    /// Name of the collector, used to report its parsing issues.
+    fn name(&self) -> &'static str;
+
+    /// Version of the collector's tables, bumped whenever their contents or schemas change.
+    fn version(&self) -> u32;
+
+    /// Applies the collector's `options` from the task file. It is called right after the
+    /// collector is created, before any file is processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `options` are not valid options of the collector, including when it takes
+    /// no options at all.
+    fn set_options(&mut self, _options: &serde_json::Value) -> Result<()> {
+        Err(anyhow::anyhow!("{} takes no options", self.name()))
+    }
+
+    /// Names of the archive members the collector reads. `process_file` is only called with
+    /// matching files.
+    ///
+    /// A `*` matches any run of characters, including `/`.
+    fn input_patterns(&self) -> &'static [&'static str];
+
+    /// Processes a single file from a benchmark run's archive. Collectors that read all their
+    /// files through `process_line` leave it as is.
     ///
     /// # Errors
     ///
     /// Returns `Err` if the file is a duplicate, cannot be read, or fails to parse.
     fn process_file(
         &mut self,
-        config: &BenchSuiteRun,
-        file: &mut dyn FileInfoInterface,
-    ) -> Result<()>;
+        _config: &BenchSuiteRun,
+        _file: &mut dyn FileInfoInterface,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether the collector reads `file_name` one line at a time through `process_line` instead
+    /// of through `process_file`. Files that every interested collector reads by lines are
+    /// streamed instead of being read into memory.
+    fn wants_lines(&self, _config: &BenchSuiteRun, _file_name: &str) -> bool {
+        false
+    }
+
+    /// Called when a file `wants_lines` accepted is opened, before any of its lines are passed
+    /// on, so collectors know the file was read even if none of its lines are for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file is a duplicate. None of its lines are passed to the collector
+    /// then, and like after a line fails to parse, the collector writes no tables.
+    fn start_lines(&mut self, _config: &BenchSuiteRun, _file_name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Processes one line, without its line ending, of a file `wants_lines` accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the line fails to parse. No further lines are passed to the collector,
+    /// which writes no tables as it only read part of the file.
+    fn process_line(
+        &mut self,
+        _config: &BenchSuiteRun,
+        _file_name: &str,
+        _line: &str,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Unified logging tag sets, e.g. `gc,phases`, of the lines of `wants_lines` files that are
+    /// passed to `process_log_line` instead of `process_line`. Each line is only split into its
+    /// decorations and message once, however many collectors read it. Lines of logs written
+    /// without the `tags` decorator are passed to every collector with `log_tags`.
+    ///
+    /// A `*` matches any run of characters.
+    fn log_tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Processes one line of a file `wants_lines` accepted that was logged with one of the
+    /// `log_tags`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the line fails to parse. No further lines are passed to the collector,
+    /// which writes no tables as it only read part of the file.
+    fn process_log_line(
+        &mut self,
+        _config: &BenchSuiteRun,
+        _file_name: &str,
+        _line: &LogLine,
+    ) -> Result<()> {
+        Ok(())
+    }
 
     /// Consumes the collector and returns the collected data as named `LazyFrame`s.
     ///
@@ -84,4 +185,227 @@ pub trait BenchSuiteCollect {
     ///
     /// Returns `Err` if the collected data cannot be assembled into a `LazyFrame`.
     fn get_result(self: Box<Self>, config: &BenchSuiteRun) -> Result<Vec<(Intern, LazyFrame)>>;
+
+    /// Names of the tables `get_result` can return.
+    ///
+    /// A `*` matches any run of characters, for tables named after the files they came from.
+    fn output_tables(&self) -> &'static [&'static str];
+
+    /// The schema `table` always has, if it is known without any data.
+    ///
+    /// Tables that do not match their declared schema are reported in the run's `parse_status`,
+    /// and declared tables that no run has data for are written empty.
+    fn output_schema(&self, _table: &str) -> Option<Schema> {
+        None
+    }
+
+    /// Tells the collector which of its tables will be written, so it can skip parsing the
+    /// inputs of the others. Collectors that cannot do better than parsing everything ignore it.
+    fn retain_tables(&mut self, _keep: &dyn Fn(&str) -> bool) {}
+}
+
+/// Options of the collectors reading unified logging files.
+///
+/// The tables of these collectors always have an `uptime` column, which is null on every row
+/// of a log without the uptime decoration, so runs logged with and without it share a schema.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogOptions {
+    /// Also write the `time` decoration as it was logged, in a `raw_clock_time` string column
+    pub keep_raw_clock_time: bool,
+}
+
+impl LogOptions {
+    /// Starts the transform of a log table's raw columns, copying `clock_time` to
+    /// `raw_clock_time` before it is parsed if the raw times are kept.
+    #[must_use]
+    pub fn lazy(&self, df: DataFrame) -> LazyFrame {
+        let lf = df.lazy();
+        if self.keep_raw_clock_time {
+            lf.with_column(col("clock_time").alias("raw_clock_time"))
+        } else {
+            lf
+        }
+    }
+}
+
+/// A table computed from the other tables of a run once all its collectors are done.
+pub trait BenchSuiteDerive {
+    /// Name of the derivation, used to report its issues.
+    fn name(&self) -> &'static str;
+
+    /// Tables the derivation reads, from collectors or other derivations. Derivations run after
+    /// the derivations they depend on, and are skipped for runs missing any of these tables.
+    fn dependencies(&self) -> &'static [&'static str];
+
+    /// Names of the tables `derive` returns.
+    fn output_tables(&self) -> &'static [&'static str];
+
+    /// Computes the derived tables from the run's tables so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the tables cannot be combined into the derived tables.
+    fn derive(
+        &self,
+        config: &BenchSuiteRun,
+        tables: &HashMap<Intern, LazyFrame>,
+    ) -> Result<Vec<(Intern, LazyFrame)>>;
+}
+
+/// The order to run `derivations` in, as indices into `derivations`: every derivation comes
+/// after the derivations producing its dependencies, keeping the given order otherwise.
+///
+/// # Errors
+///
+/// Returns `Err` if derivations depend on each other in a cycle.
+pub fn derivation_order(derivations: &[Box<dyn BenchSuiteDerive>]) -> Result<Vec<usize>> {
+    let mut pending: Vec<bool> = vec![true; derivations.len()];
+    let mut order = Vec::with_capacity(derivations.len());
+    while order.len() < derivations.len() {
+        // The first derivation none of whose dependencies are still to be produced
+        let ready = (0..derivations.len()).find(|&i| {
+            pending[i]
+                && derivations[i].dependencies().iter().all(|dep| {
+                    derivations
+                        .iter()
+                        .zip(&pending)
+                        .all(|(other, pending)| !pending || !other.output_tables().contains(dep))
+                })
+        });
+        let Some(ready) = ready else {
+            let cycle: Vec<&str> = derivations
+                .iter()
+                .zip(&pending)
+                .filter(|(_, pending)| **pending)
+                .map(|(d, _)| d.name())
+                .collect();
+            return Err(anyhow::anyhow!(
+                "Derivations depend on each other: {}",
+                cycle.join(", ")
+            ));
+        };
+        pending[ready] = false;
+        order.push(ready);
+    }
+    Ok(order)
+}
+
+/// Schema of the table `transform` produces from raw columns with schema `raw`, for
+/// collectors whose `get_result` reshapes the columns they parse.
+#[must_use]
+pub fn derive_schema(
+    raw: &Schema,
+    transform: impl FnOnce(DataFrame) -> LazyFrame,
+) -> Option<Schema> {
+    transform(DataFrame::empty_with_schema(raw))
+        .collect_schema()
+        .ok()
+        .map(Arc::unwrap_or_clone)
+}
+
+/// Passes one line of `file_name`, a file `collector` reads by lines, to `process_line`, or to
+/// `process_log_line` if the collector reads unified logging tags. `log_line` is the line split
+/// into its decorations, which is only passed on if it has one of the collector's `log_tags`.
+///
+/// # Errors
+///
+/// Returns the collector's error for the line.
+pub fn dispatch_line(
+    collector: &mut dyn BenchSuiteCollect,
+    config: &BenchSuiteRun,
+    file_name: &str,
+    line: &str,
+    log_line: Option<&LogLine>,
+) -> Result<()> {
+    let tags = collector.log_tags();
+    if tags.is_empty() {
+        collector.process_line(config, file_name, line)
+    } else if let Some(log_line) = log_line.filter(|l| {
+        l.decorations
+            .tags
+            .is_none_or(|t| tags.iter().any(|p| name_matches(p, t)))
+    }) {
+        collector.process_log_line(config, file_name, log_line)
+    } else {
+        Ok(())
+    }
+}
+
+/// Matches a table or file `name` against a `pattern` where `*` matches any run of characters.
+#[must_use]
+pub fn name_matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_matches_wildcards() {
+        assert!(name_matches("config", "config"));
+        assert!(!name_matches("config", "config_options"));
+        assert!(name_matches("dacapo_latency_*", "dacapo_latency_simple"));
+        assert!(name_matches("*_sadf", "cpu_sadf"));
+        assert!(!name_matches("*_sadf", "cpu_sadf.csv"));
+        assert!(name_matches("runner*.exit", "runner12.exit"));
+        assert!(!name_matches("a*ab", "ab"));
+    }
+
+    struct Derive(
+        &'static str,
+        &'static [&'static str],
+        &'static [&'static str],
+    );
+
+    impl BenchSuiteDerive for Derive {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+        fn dependencies(&self) -> &'static [&'static str] {
+            self.1
+        }
+        fn output_tables(&self) -> &'static [&'static str] {
+            self.2
+        }
+        fn derive(
+            &self,
+            _: &BenchSuiteRun,
+            _: &HashMap<Intern, LazyFrame>,
+        ) -> Result<Vec<(Intern, LazyFrame)>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn derivations_run_after_their_dependencies() {
+        let derivations: Vec<Box<dyn BenchSuiteDerive>> = vec![
+            Box::new(Derive("labelled", &["joined", "status"], &["labelled"])),
+            Box::new(Derive("joined", &["zgc_phases"], &["joined"])),
+            Box::new(Derive("other", &["status"], &["other"])),
+        ];
+        assert_eq!(derivation_order(&derivations).unwrap(), [1, 0, 2]);
+
+        let cycle: Vec<Box<dyn BenchSuiteDerive>> = vec![
+            Box::new(Derive("a", &["b"], &["a"])),
+            Box::new(Derive("b", &["a"], &["b"])),
+        ];
+        assert!(derivation_order(&cycle).is_err());
+    }
 }
@@ -2,7 +2,7 @@ use anyhow::{Context, Result, anyhow};
 use crossbeam::channel;
 use polars::polars_utils::compression::ZstdLevel;
 use polars::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::fs::{self, File};
 use std::io::BufReader;
@@ -12,13 +12,18 @@ use std::thread;
 
 use string_intern::Intern;
 
-use bench_suite_collect_results::{BenchSuiteCollect, FileInfo};
-use bench_suite_config::BenchSuiteTasks;
-use bench_suite_types::BenchSuiteRun;
+use bench_suite_collect_results::{
+    BenchSuiteCollect, BenchSuiteDerive, FileInfo, derivation_order, dispatch_line, name_matches,
+};
+use bench_suite_collector_regex::{BenchSuiteCollectRegex, RegexCollectorConfig};
+use bench_suite_config::{BenchSuiteTasks, RunId, TableSelection};
+use bench_suite_derive_sql::{BenchSuiteDeriveSql, SqlQuery};
+use bench_suite_types::{BenchSuiteRun, BenchmarkKind};
+use unified_log::LogLine;
 
 struct ToCollectQueue<'a, T>
 where
-    T: Iterator<Item = (u64, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)>,
+    T: Iterator<Item = (RunId, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)>,
 {
     it: Mutex<T>,
     pb: indicatif::ProgressBar,
@@ -26,7 +31,7 @@ where
 
 impl<'a, T> ToCollectQueue<'a, T>
 where
-    T: Iterator<Item = (u64, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)>,
+    T: Iterator<Item = (RunId, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)>,
 {
     fn new(input: T, progress: indicatif::ProgressBar) -> Self {
         Self {
@@ -35,7 +40,7 @@ where
         }
     }
 
-    fn consume(&self) -> Option<(u64, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)> {
+    fn consume(&self) -> Option<(RunId, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)> {
         let mut guard = self.it.lock().unwrap();
         let tmp = guard.next();
         if tmp.is_some() {
@@ -58,11 +63,14 @@ type LazyFrameSendChannel = mpsc::SyncSender<LazyFrame>;
 #[derive(Clone)]
 struct TableSubmitter<'scope, 'env> {
     source: Arc<Mutex<HashMap<DatabaseLocation, LazyFrameSendChannel>>>,
+    /// Every table a frame was submitted for. Kept apart from `source`, whose senders have to be
+    /// dropped for the parquet threads to finish
+    written: Arc<Mutex<HashSet<DatabaseLocation>>>,
     local: HashMap<DatabaseLocation, LazyFrameSendChannel>,
     scope: &'scope std::thread::Scope<'scope, 'env>,
     base_location: &'scope str,
     submit_queue: channel::Sender<ParquetSubmit>,
-    drop_tables: &'scope HashSet<Intern>,
+    tasks: &'scope BenchSuiteTasks,
 }
 
 fn parquet_thread(
@@ -74,8 +82,14 @@ fn parquet_thread(
     let mut data: Option<DataFrame> = None;
     while let Ok(msg) = rx.recv() {
         match &mut data {
+            // Runs with fields unknown to BenchSuiteRun have extra config columns
             Some(v) => {
-                v.vstack_mut(&msg.collect().unwrap()).unwrap();
+                let df = msg.collect().unwrap();
+                if v.get_column_names() == df.get_column_names() {
+                    v.vstack_mut(&df).unwrap();
+                } else {
+                    *v = polars::functions::concat_df_diagonal(&[core::mem::take(v), df]).unwrap();
+                }
             }
             None => {
                 data = Some(msg.collect().unwrap());
@@ -124,7 +138,13 @@ impl<'scope, 'env> TableSubmitter<'scope, 'env> {
         key: DatabaseLocation,
         value: LazyFrame,
     ) -> std::result::Result<(), Box<std::sync::mpsc::SendError<LazyFrame>>> {
-        if self.drop_tables.contains(&key.db_name) {
+        // parse_status is how failures are reported, so it is never dropped
+        let keep = key.db_name.as_str() == "parse_status"
+            || self
+                .tasks
+                .table_selection(key.directory.as_str())
+                .is_none_or(|selection| selection.keeps(key.db_name));
+        if !keep {
             return Ok(());
         }
         let base_location = self.base_location;
@@ -135,6 +155,7 @@ impl<'scope, 'env> TableSubmitter<'scope, 'env> {
             locked
                 .entry(key)
                 .or_insert_with(|| {
+                    self.written.lock().unwrap().insert(key);
                     let (tx, rx) = mpsc::sync_channel(1);
                     let submit = self.submit_queue.clone();
                     thread::Builder::new()
@@ -156,20 +177,162 @@ impl<'scope, 'env> TableSubmitter<'scope, 'env> {
         scope: &'scope std::thread::Scope<'scope, 'env>,
         base_location: &'scope str,
         write_channel: channel::Sender<ParquetSubmit>,
-        drop_tables: &'scope HashSet<Intern>,
+        tasks: &'scope BenchSuiteTasks,
     ) -> Self {
         Self {
             source: Arc::new(Mutex::new(HashMap::new())),
+            written: Arc::new(Mutex::new(HashSet::new())),
             local: HashMap::new(),
             scope,
             base_location,
             submit_queue: write_channel,
-            drop_tables,
+            tasks,
         }
     }
 }
 
-fn process_run(run: &BenchSuiteRun, tar_path: &Path) -> Result<HashMap<Intern, LazyFrame>> {
+/// Whether `selection` could keep a table matching the collector output `pattern`.
+fn may_keep(selection: &TableSelection, pattern: &str) -> bool {
+    if !pattern.contains('*') {
+        return selection.keeps(Intern::new(pattern));
+    }
+    // drop_tables only names exact tables, so only keep_tables can rule out a pattern
+    selection.keep_tables().is_none_or(|keep| {
+        keep.iter()
+            .any(|table| name_matches(pattern, table.as_str()) && selection.keeps(*table))
+    })
+}
+
+/// Creates the collectors of each run: those of its benchmark, with their options from the task
+/// file, and the regex collectors of the task. Also creates the derivations, including the
+/// task's SQL queries.
+struct CollectorFactory<'a> {
+    options: &'a HashMap<String, serde_json::Value>,
+    regex: Vec<Arc<RegexCollectorConfig>>,
+    sql: Vec<Arc<SqlQuery>>,
+    /// The order the derivations run in, sorted once for every run
+    derivation_order: Vec<usize>,
+}
+
+impl<'a> CollectorFactory<'a> {
+    /// Parses the regex collectors and SQL queries and checks the collector options of the task
+    /// up front, so a typo fails the task instead of every run.
+    fn new(config: &'a BenchSuiteTasks) -> Result<Self> {
+        let mut factory = Self {
+            options: config.collector_options(),
+            regex: config
+                .regex_collectors()
+                .iter()
+                .map(RegexCollectorConfig::from_value)
+                .collect::<Result<_>>()
+                .context("Invalid regex_collectors")?,
+            sql: config
+                .sql_queries()
+                .iter()
+                .map(|(table, query)| SqlQuery::from_value(table, query))
+                .collect::<Result<_>>()?,
+            derivation_order: Vec::new(),
+        };
+        // Also fails on SQL queries that read each other's tables in a cycle
+        factory.derivation_order = derivation_order(&factory.unsorted_derivations())?;
+
+        let benchmarks: HashSet<BenchmarkKind> = config
+            .to_collect()
+            .map(|(_, run, _, _)| run.benchmark)
+            .filter(|benchmark| {
+                bench_suite_benchmark_configs::get_collect_config(benchmark).is_ok()
+            })
+            .collect();
+        let mut names = HashSet::new();
+        // A list rather than a set, so a task table named like a collector's table counts twice
+        let mut tables: Vec<&str> = Vec::new();
+        for benchmark in &benchmarks {
+            for collector in factory.configured(benchmark)? {
+                names.insert(collector.name());
+                tables.extend(collector.output_tables());
+            }
+        }
+        tables.extend(factory.regex.iter().map(|regex| regex.table()));
+        for name in factory.options.keys() {
+            if !names.contains(name.as_str()) {
+                return Err(anyhow!(
+                    "collector_options has options for {name}, which no collected benchmark uses"
+                ));
+            }
+        }
+        for derivation in factory.derivations() {
+            tables.extend(derivation.output_tables());
+        }
+        // The task's tables are in tables too, so each must match exactly one declared table
+        let mut task_tables = HashSet::new();
+        let regex_tables = factory.regex.iter().map(|regex| regex.table());
+        for table in regex_tables.chain(factory.sql.iter().map(|sql| sql.table())) {
+            if !task_tables.insert(table)
+                || tables.iter().filter(|t| name_matches(t, table)).count() > 1
+            {
+                return Err(anyhow!(
+                    "The table {table} of the task file is also written by another collector"
+                ));
+            }
+        }
+        Ok(factory)
+    }
+
+    /// The collectors of `benchmark` with their options, without the regex collectors of the
+    /// task, which are named after their table and take no options.
+    fn configured(&self, benchmark: &BenchmarkKind) -> Result<Vec<Box<dyn BenchSuiteCollect>>> {
+        bench_suite_benchmark_configs::get_collect_config(benchmark)?
+            .iter()
+            .map(|create| {
+                let mut collector = create();
+                if let Some(options) = self.options.get(collector.name()) {
+                    collector
+                        .set_options(options)
+                        .with_context(|| format!("Invalid options for {}", collector.name()))?;
+                }
+                Ok(collector)
+            })
+            .collect()
+    }
+
+    fn create(&self, benchmark: &BenchmarkKind) -> Result<Vec<Box<dyn BenchSuiteCollect>>> {
+        let mut collectors = self.configured(benchmark)?;
+        collectors.extend(
+            self.regex
+                .iter()
+                .map(|config| BenchSuiteCollectRegex::boxed(Arc::clone(config))),
+        );
+        Ok(collectors)
+    }
+
+    fn unsorted_derivations(&self) -> Vec<Box<dyn BenchSuiteDerive>> {
+        let configured = bench_suite_benchmark_configs::get_derive_config()
+            .iter()
+            .map(|x| x());
+        let sql = self
+            .sql
+            .iter()
+            .map(|query| BenchSuiteDeriveSql::boxed(Arc::clone(query)));
+        configured.chain(sql).collect()
+    }
+
+    /// The derivations of every run, in the order they run in.
+    fn derivations(&self) -> Vec<Box<dyn BenchSuiteDerive>> {
+        let mut derivations: Vec<Option<Box<dyn BenchSuiteDerive>>> =
+            self.unsorted_derivations().into_iter().map(Some).collect();
+        self.derivation_order
+            .iter()
+            .filter_map(|&i| derivations[i].take())
+            .collect()
+    }
+}
+
+fn process_run(
+    run: &BenchSuiteRun,
+    tar_path: &Path,
+    keep_table: &dyn Fn(&str) -> bool,
+    factory: &CollectorFactory,
+) -> Result<HashMap<Intern, LazyFrame>> {
     let tarfile = BufReader::new(File::open(tar_path)?);
     let tarfile = xz2::read::XzDecoder::new(tarfile);
     let mut tarfile = tar::Archive::new(tarfile);
@@ -178,13 +341,33 @@ fn process_run(run: &BenchSuiteRun, tar_path: &Path) -> Result<HashMap<Intern, L
         .entries()
         .context("Failed to get entries from tar file")?;
 
-    let mut collectors: Vec<Box<dyn BenchSuiteCollect>> =
-        bench_suite_benchmark_configs::get_collect_config(&run.benchmark)?
+    // Derivations run in dependency order, so walking them backwards reaches every table a
+    // kept derived table needs, even where that table is dropped itself
+    let mut derivations = factory.derivations();
+    let mut needed: HashSet<&str> = HashSet::new();
+    for derivation in derivations.iter().rev() {
+        if derivation
+            .output_tables()
             .iter()
-            .map(|x| x())
-            .collect();
+            .any(|t| keep_table(t) || needed.contains(t))
+        {
+            needed.extend(derivation.dependencies());
+        }
+    }
+    let keep_table = |table: &str| keep_table(table) || needed.contains(table);
+    derivations.retain(|d| d.output_tables().iter().any(|t| keep_table(t)));
+
+    // Collectors whose every table is dropped by every destination are never run
+    let mut collectors = factory.create(&run.benchmark)?;
+    collectors.retain(|c| c.output_tables().iter().any(|t| keep_table(t)));
+    for collector in &mut collectors {
+        collector.retain_tables(&keep_table);
+    }
 
     let mut parsing_issues: Vec<String> = Vec::new();
+    // Collectors that failed on a line of a file, which write no tables as they only read part
+    // of it
+    let mut failed = vec![false; collectors.len()];
 
     for file in entries {
         let file = file.context("Failed to get file from tar")?;
@@ -196,27 +379,111 @@ fn process_run(run: &BenchSuiteRun, tar_path: &Path) -> Result<HashMap<Intern, L
             .to_string();
         let mut file_info = FileInfo::new(path.as_str(), file);
 
-        for i in &mut collectors {
-            if let Err(e) = i.process_file(run, &mut file_info) {
-                parsing_issues.push(format!("process_file({path}): {e:?}"));
+        // Whole-file collectors run first, so the file is only streamed if none want it
+        let mut line_collectors = Vec::new();
+        for (i, failed) in collectors.iter_mut().zip(&mut failed) {
+            if *failed || !i.input_patterns().iter().any(|p| name_matches(p, &path)) {
+                continue;
             }
+            if i.wants_lines(run, &path) {
+                if let Err(e) = i.start_lines(run, &path) {
+                    parsing_issues.push(format!("{}: start_lines({path}): {e:?}", i.name()));
+                    *failed = true;
+                } else {
+                    line_collectors.push((i, failed));
+                }
+            } else if let Err(e) = i.process_file(run, &mut file_info) {
+                parsing_issues.push(format!("{}: process_file({path}): {e:?}", i.name()));
+            }
+        }
+        if line_collectors.is_empty() {
+            continue;
+        }
+        let parse_logs = line_collectors
+            .iter()
+            .any(|(collector, _)| !collector.log_tags().is_empty());
+        let streamed = file_info.for_each_line(&mut |line| {
+            // Split once for all the collectors reading unified logging tags
+            let log_line = if parse_logs {
+                LogLine::parse(line)
+            } else {
+                None
+            };
+            for (collector, failed) in &mut line_collectors {
+                if **failed {
+                    continue;
+                }
+                if let Err(e) =
+                    dispatch_line(&mut ***collector, run, &path, line, log_line.as_ref())
+                {
+                    parsing_issues
+                        .push(format!("{}: process_line({path}): {e:?}", collector.name()));
+                    **failed = true;
+                }
+            }
+            Ok(())
+        });
+        if let Err(e) = streamed {
+            parsing_issues.push(format!("for_each_line({path}): {e:?}"));
         }
     }
 
     drop(tarfile);
 
     let mut return_map: HashMap<Intern, LazyFrame> = HashMap::new();
-    for collector in collectors {
+    for (collector, failed) in collectors.into_iter().zip(failed) {
+        if failed {
+            continue;
+        }
+        let name = collector.name();
+        let schemas: HashMap<&str, Schema> = collector
+            .output_tables()
+            .iter()
+            .filter_map(|t| Some((*t, collector.output_schema(t)?)))
+            .collect();
         match BenchSuiteCollect::get_result(collector, run) {
+            Ok(results) => {
+                for (key, mut val) in results {
+                    // A table that does not match its declared schema is still written, the
+                    // mismatch is only reported
+                    if let Some(expected) = schemas.get(key.as_str()) {
+                        match val.collect_schema() {
+                            Ok(actual) if *actual == *expected => {}
+                            Ok(actual) => parsing_issues.push(format!(
+                                "{name}: table {key} has schema {actual:?}, expected {expected:?}"
+                            )),
+                            Err(e) => parsing_issues.push(format!("{name}: table {key}: {e:?}")),
+                        }
+                    }
+                    if return_map.insert(key, val).is_some() {
+                        return Err(anyhow!("Repeated the table name {key}"));
+                    }
+                }
+            }
+            Err(e) => {
+                parsing_issues.push(format!("{name}: get_result: {e:?}"));
+            }
+        }
+    }
+
+    for derivation in &derivations {
+        let available = derivation
+            .dependencies()
+            .iter()
+            .all(|t| return_map.contains_key(&Intern::new(*t)));
+        if !available {
+            continue;
+        }
+        match derivation.derive(run, &return_map) {
             Ok(results) => {
                 for (key, val) in results {
                     if return_map.insert(key, val).is_some() {
-                        return Err(anyhow!(std::format!("Repeated the table name ")));
+                        return Err(anyhow!("Repeated the table name {key}"));
                     }
                 }
             }
             Err(e) => {
-                parsing_issues.push(format!("get_result: {e:?}"));
+                parsing_issues.push(format!("{}: derive: {e:?}", derivation.name()));
             }
         }
     }
@@ -232,12 +499,21 @@ fn process_run(run: &BenchSuiteRun, tar_path: &Path) -> Result<HashMap<Intern, L
     Ok(return_map)
 }
 
-fn process_thread<'a, T>(queue: &ToCollectQueue<'a, T>, mut submitter: TableSubmitter)
-where
-    T: Iterator<Item = (u64, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)>,
+fn process_thread<'a, T>(
+    queue: &ToCollectQueue<'a, T>,
+    mut submitter: TableSubmitter,
+    tasks: &BenchSuiteTasks,
+    factory: &CollectorFactory,
+) where
+    T: Iterator<Item = (RunId, &'a BenchSuiteRun, Vec<&'a str>, PathBuf)>,
 {
     while let Some((id, run, paths, tar_path)) = queue.consume() {
-        let map = match process_run(run, &tar_path) {
+        let selections: Vec<&TableSelection> = paths
+            .iter()
+            .filter_map(|p| tasks.table_selection(p))
+            .collect();
+        let keep_table = |table: &str| selections.iter().any(|s| may_keep(s, table));
+        let map = match process_run(run, &tar_path, &keep_table, factory) {
             Ok(v) => v,
             Err(e) => {
                 // process_run itself failed
@@ -250,7 +526,19 @@ where
         };
 
         for (key, mut val) in map {
-            val = val.with_column(lit(id).alias("id"));
+            val = val.with_columns([
+                lit(id.id).alias("id"),
+                lit(id.source.as_str()).alias("source"),
+            ]);
+            // The label depends on the runs of the collection the table is written to
+            let labelled = |directory: &str, val: LazyFrame| {
+                if key.as_str() == "config" {
+                    let label = tasks.config_label(directory, run).unwrap_or_default();
+                    val.with_column(lit(label).alias("config_label"))
+                } else {
+                    val
+                }
+            };
             if let Some((last, remaining)) = paths.split_last() {
                 for p in remaining {
                     submitter
@@ -259,7 +547,7 @@ where
                                 directory: Intern::new(*p),
                                 db_name: key,
                             },
-                            val.clone(),
+                            labelled(p, val.clone()),
                         )
                         .unwrap();
                 }
@@ -269,7 +557,7 @@ where
                             directory: Intern::new(*last),
                             db_name: key,
                         },
-                        val,
+                        labelled(last, val),
                     )
                     .unwrap();
             }
@@ -277,17 +565,175 @@ where
     }
 }
 
+struct Args {
+    config_file_path: PathBuf,
+    /// Treat validation warnings as errors
+    strict: bool,
+    /// Only validate the task file, without collecting
+    validate: bool,
+    /// Write the catalog of all runs to this parquet file instead of collecting
+    catalog: Option<PathBuf>,
+}
+
+/// Prints the run fields that collections can filter on, grouped by category.
+fn list_fields() {
+    let mut category = None;
+    for field in BenchSuiteRun::FIELDS {
+        if category != Some(field.category) {
+            category = Some(field.category);
+            println!("{}:", field.category);
+        }
+        let optional = if field.optional { " (optional)" } else { "" };
+        println!("    {}: {}{optional}", field.name, field.type_name);
+    }
+}
+
+impl Args {
+    fn parse() -> Self {
+        let mut config_file_path = None;
+        let mut strict = false;
+        let mut validate = false;
+        let mut catalog = None;
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--strict" => strict = true,
+                "--validate" => validate = true,
+                "--list-fields" => {
+                    list_fields();
+                    std::process::exit(0);
+                }
+                "--catalog" => {
+                    catalog = Some(PathBuf::from(
+                        args.next().expect("--catalog needs the path to write to"),
+                    ));
+                }
+                _ if config_file_path.is_none() => config_file_path = Some(PathBuf::from(arg)),
+                _ => panic!("Unexpected argument {arg}"),
+            }
+        }
+        Self {
+            config_file_path: config_file_path
+                .expect("You need to provide a an argument for the path"),
+            strict,
+            validate,
+            catalog,
+        }
+    }
+}
+
+/// Writes every run in the task's locations to one parquet file, without opening any archives.
+fn write_catalog(config: &BenchSuiteTasks, path: &Path) -> Result<()> {
+    let mut catalog = config.run_catalog()?;
+    catalog = polars_helpers::shrink_int_columns(&catalog)?;
+    ParquetWriter::new(File::create(path).context("Failed to create the catalog file")?)
+        .with_compression(ParquetCompression::Zstd(Some(ZstdLevel::try_new(18)?)))
+        .with_statistics(StatisticsOptions::default())
+        .finish(&mut catalog)?;
+    Ok(())
+}
+
+/// Records the collector versions each collection was built with in `collectors.json`, and
+/// writes an empty file for every declared table with a known schema that none of the
+/// collection's runs had data for, so readers see the table instead of a missing file.
+fn finish_collections(
+    config: &BenchSuiteTasks,
+    factory: &CollectorFactory,
+    written: &HashSet<DatabaseLocation>,
+) -> Result<()> {
+    let mut benchmarks: BTreeMap<&str, HashSet<BenchmarkKind>> = config
+        .collection_names()
+        .map(|name| (name, HashSet::new()))
+        .collect();
+    for (_, run, paths, _) in config.to_collect() {
+        for path in paths {
+            benchmarks.entry(path).or_default().insert(run.benchmark);
+        }
+    }
+
+    let base_path = config.output_path();
+    for (name, kinds) in benchmarks {
+        let mut versions: BTreeMap<&str, u32> = BTreeMap::new();
+        // Runs of benchmarks without a collection config only get a validation warning
+        let kinds = kinds
+            .iter()
+            .filter(|kind| bench_suite_benchmark_configs::get_collect_config(kind).is_ok());
+        for kind in kinds {
+            for collector in factory.create(kind)? {
+                versions.insert(collector.name(), collector.version());
+                for table in collector.output_tables() {
+                    let location = DatabaseLocation {
+                        directory: Intern::new(name),
+                        db_name: Intern::new(table),
+                    };
+                    let keep = config
+                        .table_selection(name)
+                        .is_none_or(|selection| selection.keeps(location.db_name));
+                    if !keep || written.contains(&location) {
+                        continue;
+                    }
+                    let Some(mut schema) = collector.output_schema(table) else {
+                        continue;
+                    };
+                    schema.with_column("id".into(), DataType::UInt64);
+                    schema.with_column("source".into(), DataType::String);
+                    // Shrunk like the files with rows, so the table has the same schema
+                    let mut df =
+                        polars_helpers::shrink_int_columns(&DataFrame::empty_with_schema(&schema))?;
+                    let path = base_path.join(name).join(format!("{table}_0.parquet"));
+                    ParquetWriter::new(File::create(path)?).finish(&mut df)?;
+                }
+            }
+        }
+        let file = File::create(base_path.join(name).join("collectors.json"))
+            .context("Failed to create collectors.json")?;
+        serde_json::to_writer_pretty(file, &versions)?;
+    }
+    Ok(())
+}
+
 fn main() {
-    let config_file_path = PathBuf::from(
-        env::args()
-            .nth(1)
-            .expect("You need to provide a an argument for the path"),
-    );
+    let args = Args::parse();
+
+    let config = BenchSuiteTasks::new(&args.config_file_path).unwrap();
 
-    let config = BenchSuiteTasks::new(&config_file_path).unwrap();
+    if let Some(catalog_path) = &args.catalog {
+        write_catalog(&config, catalog_path).unwrap();
+        return;
+    }
+
+    let unknown_fields = config.unknown_run_fields();
+    if !unknown_fields.is_empty() {
+        let unknown_fields: Vec<&str> = unknown_fields.into_iter().collect();
+        eprintln!(
+            "warning: status.json has fields without a typed BenchSuiteRun field, they are \
+             collected as strings: {}",
+            unknown_fields.join(", ")
+        );
+    }
+
+    let issues = config
+        .validate(|benchmark| bench_suite_benchmark_configs::get_collect_config(benchmark).is_ok());
+    let severity = if args.strict { "error" } else { "warning" };
+    for issue in &issues {
+        eprintln!("{severity}: {issue}");
+    }
+    if args.strict && !issues.is_empty() {
+        std::process::exit(1);
+    }
+    let factory = CollectorFactory::new(&config).unwrap_or_else(|e| {
+        eprintln!("error: {e:#}");
+        std::process::exit(1);
+    });
+    if args.validate {
+        if issues.is_empty() {
+            eprintln!("No issues found");
+        }
+        return;
+    }
 
     // Delete existing collection directories and create fresh ones
-    let base_path = config.get_path();
+    let base_path = config.output_path();
     for name in config.collection_names() {
         let collection_path = base_path.join(name);
         if collection_path.exists() {
@@ -295,6 +741,9 @@ fn main() {
                 .expect("Failed to delete existing collection directory");
         }
         fs::create_dir_all(&collection_path).expect("Failed to create collection directory");
+        let metadata = File::create(collection_path.join("collection.json"))
+            .expect("Failed to create collection.json");
+        config.write_collection_metadata(name, metadata).unwrap();
     }
 
     let progress = indicatif::MultiProgress::new();
@@ -314,12 +763,12 @@ fn main() {
 
     let (write_send, write_recieve) = channel::bounded(5);
 
-    std::thread::scope(|x| {
+    let written = std::thread::scope(|x| {
         let s = TableSubmitter::new(
             x,
-            config.get_path().to_str().unwrap(),
+            config.output_path().to_str().unwrap(),
             write_send,
-            config.get_drop_tables(),
+            &config,
         );
         for i in 0..16 {
             let tmp_recieve = write_recieve.clone();
@@ -331,9 +780,14 @@ fn main() {
         for _ in 0..16 {
             let tmp_s = s.clone();
             x.spawn(|| {
-                process_thread(&queue, tmp_s);
+                process_thread(&queue, tmp_s, &config, &factory);
             });
         }
+        let written = Arc::clone(&s.written);
         drop(s);
+        written
     });
+
+    let written = std::mem::take(&mut *written.lock().unwrap());
+    finish_collections(&config, &factory, &written).unwrap();
 }
@@ -0,0 +1,224 @@
+//! Runs the collection over a result location generated from the runs in `fixtures/`, and
+//! checks the tables written to each collection.
+
+use bench_suite_collector_testkit::{ResultLocation, RunBuilder};
+use polars::prelude::*;
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ZGC_RUN: u64 = 0x1A;
+const G1_RUN: u64 = 0x2B;
+/// How long a collection may take before the test fails instead of hanging.
+const COLLECT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Writes a location with a ZGC and a G1 run of `dacapo_samples2` below the test's target
+/// directory, and returns its path.
+fn write_location(name: &str) -> PathBuf {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    let mut location =
+        ResultLocation::new(Path::new(env!("CARGO_TARGET_TMPDIR")).join(name)).unwrap();
+    location
+        .add_run_from_dir(
+            ZGC_RUN,
+            RunBuilder::new("dacapo_samples2")
+                .field("gc", "ZGC")
+                .field("dacapo_benchmark", "h2"),
+            &fixtures.join("dacapo_zgc"),
+        )
+        .unwrap()
+        .add_run_from_dir(
+            G1_RUN,
+            RunBuilder::new("dacapo_samples2")
+                .field("gc", "G1")
+                .field("dacapo_benchmark", "h2"),
+            &fixtures.join("dacapo_g1"),
+        )
+        .unwrap();
+    location.finish().unwrap()
+}
+
+/// Runs `bench-suite-collect` with `args` on the task file `task`, written into `location`.
+/// Fails if it takes longer than `COLLECT_TIMEOUT`, so a collection that hangs fails the test.
+fn run_collect(location: &Path, task: &serde_json::Value, args: &[&str]) -> ExitStatus {
+    let task_path = location.join("task.json");
+    fs::write(&task_path, serde_json::to_vec_pretty(task).unwrap()).unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bench-suite-collect"))
+        .args(args)
+        .arg(&task_path)
+        .spawn()
+        .unwrap();
+    let deadline = Instant::now() + COLLECT_TIMEOUT;
+    loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            return status;
+        }
+        if Instant::now() > deadline {
+            child.kill().unwrap();
+            child.wait().unwrap();
+            panic!("bench-suite-collect did not finish in {COLLECT_TIMEOUT:?}");
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Collects the task file `task`, written into `location`.
+fn collect(location: &Path, task: &serde_json::Value) {
+    let status = run_collect(location, task, &[]);
+    assert!(status.success(), "bench-suite-collect failed with {status}");
+}
+
+/// The parquet files of a collection.
+fn table_files(collection: &Path) -> BTreeSet<String> {
+    fs::read_dir(collection)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.ends_with(".parquet"))
+        .collect()
+}
+
+fn read_table(collection: &Path, table: &str) -> DataFrame {
+    let path = collection.join(format!("{table}_0.parquet"));
+    let file = File::open(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    ParquetReader::new(file).finish().unwrap()
+}
+
+/// The sorted ids of the runs a table has rows of.
+fn run_ids(df: &DataFrame) -> Vec<u64> {
+    let ids = df.column("id").unwrap().cast(&DataType::UInt64).unwrap();
+    let mut ids: Vec<u64> = ids.u64().unwrap().into_no_null_iter().collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[test]
+fn collections_get_the_tables_of_their_runs() {
+    let location = write_location("collections_get_the_tables_of_their_runs");
+    collect(
+        &location,
+        &serde_json::json!({
+            "location": location,
+            "drop_tables": ["config_options"],
+            "collect": {
+                "all": {},
+                "zgc": {"gc": ["ZGC"], "drop_tables": ["threadstat_event"]},
+                "workers": {"keep_tables": ["zgc_phase_workers"]},
+            },
+        }),
+    );
+
+    for collection in ["all", "zgc", "workers"] {
+        let dir = location.join(collection);
+        assert!(dir.join("collection.json").exists());
+        assert!(dir.join("collectors.json").exists());
+        let files = table_files(&dir);
+        assert!(
+            !files.contains("parse_status_0.parquet"),
+            "{collection} has parse issues: {:?}",
+            read_table(&dir, "parse_status")
+        );
+        assert!(!files.contains("config_options_0.parquet"));
+    }
+
+    // Every run is written to every collection it is in
+    let all = location.join("all");
+    let zgc = location.join("zgc");
+    assert_eq!(run_ids(&read_table(&all, "config")), [ZGC_RUN, G1_RUN]);
+    assert_eq!(run_ids(&read_table(&zgc, "config")), [ZGC_RUN]);
+    assert_eq!(
+        run_ids(&read_table(&all, "dacapo_iteration")),
+        [ZGC_RUN, ZGC_RUN, G1_RUN]
+    );
+    assert_eq!(
+        run_ids(&read_table(&zgc, "dacapo_iteration")),
+        [ZGC_RUN, ZGC_RUN]
+    );
+    for table in [
+        "zgc_gc_summary",
+        "zgc_phases",
+        "java_threads",
+        "load_sadf",
+        "dacapo_latency_simple",
+        "threadstat_counter_threads",
+        "time",
+    ] {
+        let rows = read_table(&all, table);
+        assert!(rows.height() > 0, "{table} is empty");
+        assert_eq!(rows, read_table(&zgc, table), "{table} differs");
+    }
+    let status = read_table(&all, "status");
+    assert_eq!(
+        status
+            .column("status")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<&str>>(),
+        ["success", "success"]
+    );
+
+    // Only the G1 run has a G1 log, which the ZGC collectors leave alone
+    let g1_phases = read_table(&all, "g1_phases");
+    assert_eq!(run_ids(&g1_phases), [G1_RUN; 5]);
+    assert_eq!(
+        g1_phases
+            .column("gc_type")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<BTreeSet<&str>>(),
+        BTreeSet::from(["Pause Young (Normal)"])
+    );
+    assert_eq!(read_table(&zgc, "g1_phases").height(), 0);
+
+    // The G1 run has none of the tables the derivations read, so they are skipped for it
+    // instead of failing, which the parse_status check above would catch
+    assert_eq!(
+        run_ids(&read_table(&all, "zgc_phase_workers")),
+        [ZGC_RUN; 4]
+    );
+    assert!(
+        run_ids(&read_table(&all, "threadstat_counter_threads"))
+            .iter()
+            .all(|id| *id == ZGC_RUN)
+    );
+
+    // The failed read in threadstat-event.csv is left out, and zgc drops the whole table
+    assert_eq!(read_table(&all, "threadstat_event").height(), 3);
+    assert!(!table_files(&zgc).contains("threadstat_event_0.parquet"));
+
+    // A derived table is written without the tables it is derived from
+    let workers = location.join("workers");
+    assert_eq!(
+        table_files(&workers),
+        BTreeSet::from(["zgc_phase_workers_0.parquet".to_string()])
+    );
+    let phase_workers = read_table(&workers, "zgc_phase_workers");
+    assert_eq!(run_ids(&phase_workers), [ZGC_RUN; 4]);
+    let num_workers = phase_workers
+        .column("num_workers")
+        .unwrap()
+        .cast(&DataType::UInt32)
+        .unwrap();
+    let mut num_workers: Vec<u32> = num_workers.u32().unwrap().into_no_null_iter().collect();
+    num_workers.sort_unstable();
+    // Three phases of the young collection with 2 workers, one of the old with 1
+    assert_eq!(num_workers, [1, 2, 2, 2]);
+}
+
+#[test]
+fn strict_turns_validation_warnings_into_errors() {
+    let location = write_location("strict_turns_validation_warnings_into_errors");
+    // No run used Shenandoah, which is a warning about the task file
+    let task = serde_json::json!({
+        "location": location,
+        "collect": {"shenandoah": {"gc": ["Shenandoah"]}},
+    });
+    assert!(run_collect(&location, &task, &["--validate"]).success());
+    assert!(!run_collect(&location, &task, &["--strict", "--validate"]).success());
+}
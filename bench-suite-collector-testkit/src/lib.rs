@@ -0,0 +1,221 @@
+use anyhow::{Context, Result};
+use bench_suite_collect_results::{BenchSuiteCollect, FileInfo, dispatch_line, name_matches};
+use bench_suite_types::BenchSuiteRun;
+use polars::prelude::*;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use string_intern::Intern;
+use unified_log::LogLine;
+
+mod location;
+
+pub use location::ResultLocation;
+
+/// A run archive member held in memory, read through the same `FileInfo` as the members of real
+/// archives.
+#[must_use]
+pub fn memory_file<'a>(name: &'a str, content: &'a [u8]) -> FileInfo<'a, &'a [u8]> {
+    FileInfo::new(name, content)
+}
+
+/// Builds the `BenchSuiteRun` a collector is tested with from the fields of a `status.json`
+/// entry, e.g. `RunBuilder::new("dacapo_samples2").field("gc", "ZGC").build()`.
+#[derive(Debug, Clone)]
+pub struct RunBuilder {
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RunBuilder {
+    #[must_use]
+    pub fn new(benchmark: &str) -> Self {
+        let mut fields = serde_json::Map::new();
+        fields.insert("benchmark".into(), benchmark.into());
+        fields.insert("tar_file".into(), "runs/0000000000000000.tar.xz".into());
+        fields.insert("iteration".into(), 0.into());
+        Self { fields }
+    }
+
+    #[must_use]
+    pub fn field(mut self, name: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.fields.insert(name.into(), value.into());
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if a field has a value `status.json` could not have.
+    #[must_use]
+    pub fn build(self) -> BenchSuiteRun {
+        serde_json::from_value(serde_json::Value::Object(self.fields))
+            .expect("RunBuilder fields do not make a BenchSuiteRun")
+    }
+}
+
+/// Runs `collector` over `files`, given as archive member names with their contents, the way a
+/// run's archive is collected, and returns its tables.
+///
+/// # Errors
+///
+/// Returns the first error of the collector, which the collection would report as a parse
+/// issue of the run.
+pub fn run_on_files<'a>(
+    mut collector: Box<dyn BenchSuiteCollect>,
+    run: &BenchSuiteRun,
+    files: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) -> Result<Vec<(Intern, LazyFrame)>> {
+    for (name, content) in files {
+        if !collector
+            .input_patterns()
+            .iter()
+            .any(|p| name_matches(p, name))
+        {
+            continue;
+        }
+        let mut file = memory_file(name, content);
+        let result = if collector.wants_lines(run, name) {
+            collector
+                .start_lines(run, name)
+                .with_context(|| format!("{}: {name}", collector.name()))?;
+            let parse_logs = !collector.log_tags().is_empty();
+            file.for_each_line(&mut |line| {
+                let log_line = if parse_logs {
+                    LogLine::parse(line)
+                } else {
+                    None
+                };
+                dispatch_line(collector.as_mut(), run, name, line, log_line.as_ref())
+            })
+        } else {
+            collector.process_file(run, &mut file)
+        };
+        result.with_context(|| format!("{}: {name}", collector.name()))?;
+    }
+    collector.get_result(run)
+}
+
+/// Runs `collector` over the files below `dir`, named by their path relative to `dir` as if
+/// they were the members of a run's archive. See `run_on_files`.
+///
+/// # Errors
+///
+/// Returns `Err` if the files cannot be read, or the first error of the collector.
+pub fn run_on_dir(
+    collector: Box<dyn BenchSuiteCollect>,
+    run: &BenchSuiteRun,
+    dir: &Path,
+) -> Result<Vec<(Intern, LazyFrame)>> {
+    let files = read_dir_files(dir)?;
+    run_on_files(
+        collector,
+        run,
+        files
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.as_slice())),
+    )
+}
+
+/// Reads the files below `dir`, named by their path relative to `dir`, sorted by name.
+fn read_dir_files(dir: &Path) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut paths = Vec::new();
+    list_files(dir, &mut paths)?;
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let name = path
+                .strip_prefix(dir)?
+                .to_str()
+                .context("Fixture file names must be UTF-8")?
+                .replace('\\', "/");
+            let content = fs::read(path).with_context(|| format!("Failed to read {name}"))?;
+            Ok((name, content))
+        })
+        .collect()
+}
+
+fn list_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to list {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Writes the schema and rows of every table, sorted by table name. Durations are written as
+/// their count of the unit in the schema.
+///
+/// # Errors
+///
+/// Returns `Err` if a table fails to collect.
+pub fn render_tables(mut tables: Vec<(Intern, LazyFrame)>) -> Result<String> {
+    tables.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+    let mut rendered = String::new();
+    for (name, lf) in tables {
+        let mut df = lf
+            .collect()
+            .with_context(|| format!("Failed to collect {name}"))?;
+        writeln!(rendered, "# {name}")?;
+        for (column, dtype) in df.schema().iter() {
+            writeln!(rendered, "{column}: {dtype}")?;
+        }
+        // The CSV writer has no format for durations, so they are written as a count of units
+        let durations: Vec<Column> = df
+            .get_columns()
+            .iter()
+            .filter(|column| matches!(column.dtype(), DataType::Duration(_)))
+            .map(|column| column.cast(&DataType::Int64))
+            .collect::<PolarsResult<_>>()?;
+        for column in durations {
+            df.with_column(column)?;
+        }
+        let mut csv = Vec::new();
+        CsvWriter::new(&mut csv)
+            .finish(&mut df)
+            .with_context(|| format!("Failed to write {name}"))?;
+        rendered.push_str(&String::from_utf8(csv)?);
+        rendered.push('\n');
+    }
+    Ok(rendered)
+}
+
+/// Compares `tables` to the snapshot `{name}.snap` in `dir`, which holds their schemas and rows.
+///
+/// Set `UPDATE_SNAPSHOTS` to write snapshots that are missing or differ instead of failing.
+///
+/// # Panics
+///
+/// Panics if the tables differ from the snapshot, the snapshot is missing, or it cannot be read
+/// or written.
+pub fn assert_snapshot(dir: &Path, name: &str, tables: Vec<(Intern, LazyFrame)>) {
+    let rendered = render_tables(tables).expect("Failed to render the tables");
+    let path = dir.join(format!("{name}.snap"));
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == rendered => {}
+        Ok(expected) if !update => {
+            panic!(
+                "The tables differ from the snapshot {}, rerun with UPDATE_SNAPSHOTS=1 if that \
+                 is intended\n--- expected\n{expected}\n--- actual\n{rendered}",
+                path.display()
+            );
+        }
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            panic!("Failed to read the snapshot {}: {e}", path.display());
+        }
+        Err(_) if !update => {
+            panic!(
+                "The snapshot {} is missing, rerun with UPDATE_SNAPSHOTS=1 to write it\n{rendered}",
+                path.display()
+            );
+        }
+        _ => {
+            fs::create_dir_all(dir).expect("Failed to create the snapshot directory");
+            fs::write(&path, rendered).expect("Failed to write the snapshot");
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{RunBuilder, read_dir_files};
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// Builds a bench-suite result location on disk, a `status.json` listing the runs with an
+/// archive `runs/{id:016X}.tar.xz` for each, to run the whole collection against.
+#[derive(Debug)]
+pub struct ResultLocation {
+    dir: PathBuf,
+    runs: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ResultLocation {
+    /// Starts an empty location in `dir`, removing whatever was there before.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `dir` cannot be removed or created.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        }
+        fs::create_dir_all(dir.join("runs"))
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        Ok(Self {
+            dir,
+            runs: serde_json::Map::new(),
+        })
+    }
+
+    /// Adds the run `id`, whose archive holds `files`, given as member names with their
+    /// contents. Its `tar_file` is set to the archive's path.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the archive cannot be written.
+    pub fn add_run<'a>(
+        &mut self,
+        id: u64,
+        run: RunBuilder,
+        files: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+    ) -> Result<&mut Self> {
+        let tar_file = format!("runs/{id:016X}.tar.xz");
+        let path = self.dir.join(&tar_file);
+        let archive =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut builder = tar::Builder::new(xz2::write::XzEncoder::new(archive, 6));
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            builder
+                .append_data(&mut header, name, content)
+                .with_context(|| format!("Failed to add {name} to {tar_file}"))?;
+        }
+        builder
+            .into_inner()?
+            .finish()
+            .with_context(|| format!("Failed to finish {tar_file}"))?;
+
+        let run = run.field("tar_file", tar_file);
+        self.runs
+            .insert(id.to_string(), serde_json::Value::Object(run.fields));
+        Ok(self)
+    }
+
+    /// Adds the run `id`, whose archive holds the files below `dir`, named by their path
+    /// relative to `dir`. See `add_run`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the files cannot be read or the archive cannot be written.
+    pub fn add_run_from_dir(&mut self, id: u64, run: RunBuilder, dir: &Path) -> Result<&mut Self> {
+        let files = read_dir_files(dir)?;
+        self.add_run(
+            id,
+            run,
+            files
+                .iter()
+                .map(|(name, content)| (name.as_str(), content.as_slice())),
+        )
+    }
+
+    /// Writes `status.json` and returns the directory of the location.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `status.json` cannot be written.
+    pub fn finish(self) -> Result<PathBuf> {
+        let status = serde_json::json!({
+            "bench_index": self.runs.len(),
+            "benchmark_runs": self.runs,
+        });
+        let path = self.dir.join("status.json");
+        fs::write(&path, serde_json::to_vec_pretty(&status)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(self.dir)
+    }
+}
@@ -1,66 +1,153 @@
-use anyhow::{Context, Result};
-use serde::Deserialize;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fs::File,
-    io::BufReader,
-    path::{self, PathBuf},
+    io::{BufReader, Write},
+    path::PathBuf,
 };
 
-use bench_suite_types::{BenchSuiteConfig, BenchSuiteRun};
+use bench_suite_types::{
+    BenchSuiteConfig, BenchSuiteRun, ConfigOption, RuleViolation, parse_jvm_options,
+};
+use polars::prelude::{Column, DataFrame};
 use string_intern::Intern;
 
+mod task_file;
+mod validation;
+
+pub use validation::ValidationIssue;
+
 pub struct BenchSuiteTasks {
-    runs: HashMap<u64, BenchSuiteRun>,
-    collections: HashMap<String, BenchSuiteConfig>,
-    location: PathBuf,
+    runs: HashMap<RunId, BenchSuiteRun>,
+    /// The collected runs that break the rules of their benchmark, checked when the status files
+    /// are loaded
+    rule_violations: Vec<(RunId, RuleViolation)>,
+    collections: HashMap<String, BenchSuiteCollection>,
+    sources: HashMap<Intern, PathBuf>,
+    output: PathBuf,
+    /// The `options` of each collector, by collector name
+    collector_options: HashMap<String, serde_json::Value>,
+    regex_collectors: Vec<serde_json::Value>,
+    sql: BTreeMap<String, serde_json::Value>,
+}
+
+/// Identifies a run across all of the bench-suite result locations of a task.
+///
+/// Run ids are only unique within one `status.json`, so they are namespaced by the name of the
+/// location they came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RunId {
+    pub source: Intern,
+    pub id: u64,
+}
+
+/// The tables written to a collection.
+///
+/// A table is written if it is in `keep_tables` (or `keep_tables` is absent) and it is not in
+/// `drop_tables`.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct TableSelection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    keep_tables: Option<HashSet<Intern>>,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     drop_tables: HashSet<Intern>,
 }
 
+impl TableSelection {
+    #[must_use]
+    pub fn keeps(&self, table: Intern) -> bool {
+        !self.drop_tables.contains(&table)
+            && self
+                .keep_tables
+                .as_ref()
+                .is_none_or(|keep| keep.contains(&table))
+    }
+
+    #[must_use]
+    pub fn keep_tables(&self) -> Option<&HashSet<Intern>> {
+        self.keep_tables.as_ref()
+    }
+}
+
 impl BenchSuiteTasks {
-    /// Get the `BenchSuiteTasks` at a folder.
+    /// Get the `BenchSuiteTasks` described by a JSON, TOML or YAML task file.
     ///
     /// # Errors
     ///
-    /// Returns `Err` if the task file at `config_file_path` cannot be opened or parsed as JSON.
-    /// Returns `Err` if the `status.json` file in the configured location cannot be opened or parsed as JSON.
-    /// Returns `Err` if any run ID key in the status file cannot be parsed as a `u64`.
+    /// Returns `Err` if the task file at `config_file_path` or one of its includes cannot be
+    /// opened or parsed, or uses an undefined variable.
+    /// Returns `Err` if the `status.json` file in any configured location cannot be opened or parsed as JSON.
+    /// Returns `Err` if any run ID key in a status file cannot be parsed as a `u64`.
+    /// Returns `Err` if two locations have the same name, or there are several locations and no output.
     pub fn new(config_file_path: &Path) -> Result<Self> {
-        let task_file = BufReader::new(File::open(config_file_path).context(std::format!(
-            "Failed to open task file {}",
-            config_file_path.display()
-        ))?);
-
         let task_config: BenchSuiteTaskConfig =
-            serde_json::from_reader(task_file).context("Failed to parse task_file")?;
+            serde_json::from_value(task_file::load(config_file_path)?)
+                .context("Failed to parse task_file")?;
 
-        let bench_suite_location = path::Path::new(&task_config.location);
-        let status_location = bench_suite_location.join("status.json");
-        let status_reader = BufReader::new(
-            File::open(&status_location).context("Failed to open status file specified")?,
-        );
-        let status: BenchSuiteStatus =
-            serde_json::from_reader(status_reader).context("Failed to parse status file")?;
+        let sources = task_config.location.sources()?;
+        let output = match (&task_config.output, sources.len()) {
+            (Some(output), _) => PathBuf::from(output),
+            (None, 1) => sources
+                .values()
+                .next()
+                .cloned()
+                .context("The task has no location")?,
+            (None, _) => bail!("A task with several locations needs an output directory"),
+        };
 
-        let BenchSuiteStatus {
-            bench_index: _,
-            benchmark_runs,
-        } = status;
+        let mut benchmark_runs = HashMap::new();
+        for (source, location) in &sources {
+            for (id, run) in load_status(location)? {
+                benchmark_runs.insert(
+                    RunId {
+                        source: *source,
+                        id,
+                    },
+                    run,
+                );
+            }
+        }
 
-        let benchmark_runs: Result<HashMap<u64, BenchSuiteRun>, std::num::ParseIntError> =
-            benchmark_runs
-                .into_iter()
-                .map(|(key, val)| key.parse::<u64>().map(|parsed| (parsed, val)))
-                .collect();
-        let benchmark_runs =
-            benchmark_runs.context(std::format!("The runs in {}", status_location.display()))?;
+        let BenchSuiteTaskConfig {
+            location: _,
+            output: _,
+            collect: mut collections,
+            drop_tables,
+            collector_options,
+            regex_collectors,
+            sql,
+        } = task_config;
+
+        // The top level drop_tables applies to every collection
+        for collection in collections.values_mut() {
+            collection
+                .tables
+                .drop_tables
+                .extend(drop_tables.iter().copied());
+        }
+
+        let mut collections = expand_splits(collections, &benchmark_runs)?;
+        for collection in collections.values_mut() {
+            collection.varying_fields = varying_fields(
+                benchmark_runs
+                    .values()
+                    .filter(|run| collection.contains(run)),
+            );
+        }
+
+        let rule_violations = collected_rule_violations(&benchmark_runs, &collections);
 
         Ok(Self {
             runs: benchmark_runs,
-            collections: task_config.collect,
-            location: bench_suite_location.to_path_buf(),
-            drop_tables: task_config.drop_tables,
+            rule_violations,
+            collections,
+            sources,
+            output,
+            collector_options,
+            regex_collectors,
+            sql,
         })
     }
 
@@ -68,22 +155,113 @@ impl BenchSuiteTasks {
         self.collections.keys().map(String::as_str)
     }
 
+    /// Names of the run fields in the status files that `BenchSuiteRun` has no typed field for.
+    #[must_use]
+    pub fn unknown_run_fields(&self) -> BTreeSet<&str> {
+        self.runs
+            .values()
+            .flat_map(|run| run.extra.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// The directory the collections are written to.
     #[must_use]
-    pub fn get_path(&self) -> &PathBuf {
-        &self.location
+    pub fn output_path(&self) -> &PathBuf {
+        &self.output
     }
 
+    /// The options the task file gives each collector, by collector name. They are checked by
+    /// the collectors themselves when they are created.
     #[must_use]
-    pub fn get_drop_tables(&self) -> &HashSet<Intern> {
-        &self.drop_tables
+    pub fn collector_options(&self) -> &HashMap<String, serde_json::Value> {
+        &self.collector_options
     }
 
+    /// The task's regex collectors, which run for every benchmark. They are checked when they
+    /// are parsed by the collector crate.
     #[must_use]
-    pub fn tar_file_path(&self, id: u64) -> PathBuf {
-        self.location.join("runs").join(format!("{id:016X}.tar.xz"))
+    pub fn regex_collectors(&self) -> &[serde_json::Value] {
+        &self.regex_collectors
     }
 
-    pub fn to_collect(&self) -> impl Iterator<Item = (u64, &BenchSuiteRun, Vec<&str>, PathBuf)> {
+    /// The task's SQL queries by the name of the table each computes. They run over every run's
+    /// tables, after its collectors and derivations.
+    #[must_use]
+    pub fn sql_queries(&self) -> &BTreeMap<String, serde_json::Value> {
+        &self.sql
+    }
+
+    #[must_use]
+    pub fn table_selection(&self, collection: &str) -> Option<&TableSelection> {
+        self.collections.get(collection).map(|c| &c.tables)
+    }
+
+    /// A short label of `run` made of the fields that vary within `collection`, e.g.
+    /// `gc=ZGC, memory_ratio=2.5`. Fields the run does not set are shown as `none`.
+    #[must_use]
+    pub fn config_label(&self, collection: &str, run: &BenchSuiteRun) -> Option<String> {
+        let collection = self.collections.get(collection)?;
+        let values: HashMap<&str, String> = run.config_values().into_iter().collect();
+        Some(
+            collection
+                .varying_fields
+                .iter()
+                .map(|field| {
+                    let value = values.get(field.as_str()).map_or("none", String::as_str);
+                    format!("{field}={value}")
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+    }
+
+    #[must_use]
+    pub fn tar_file_path(&self, run: RunId) -> PathBuf {
+        self.sources[&run.source]
+            .join("runs")
+            .join(format!("{:016X}.tar.xz", run.id))
+    }
+
+    /// Every run of every location as one table with a row per run, identified by its `id` and
+    /// `source` columns. No run archives are opened.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if Polars fails to build the table.
+    pub fn run_catalog(&self) -> Result<DataFrame> {
+        let mut ids: Vec<RunId> = self.runs.keys().copied().collect();
+        ids.sort_by(|a, b| (a.source.as_str(), a.id).cmp(&(b.source.as_str(), b.id)));
+
+        let mut catalog = BenchSuiteRun::to_config_df(ids.iter().map(|id| &self.runs[id]))
+            .context("Failed to build the run catalog")?;
+        catalog.with_column(Column::new(
+            "id".into(),
+            ids.iter().map(|id| id.id).collect::<Vec<u64>>(),
+        ))?;
+        catalog.with_column(Column::new(
+            "source".into(),
+            ids.iter()
+                .map(|id| id.source.as_str())
+                .collect::<Vec<&str>>(),
+        ))?;
+        Ok(catalog)
+    }
+
+    /// Writes the filter, split values and table selection of `collection` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if there is no collection called `collection` or writing fails.
+    pub fn write_collection_metadata(&self, collection: &str, writer: impl Write) -> Result<()> {
+        let metadata = self
+            .collections
+            .get(collection)
+            .context(std::format!("No collection called {collection}"))?;
+        serde_json::to_writer_pretty(writer, metadata)
+            .context(std::format!("Failed to write the metadata of {collection}"))
+    }
+
+    pub fn to_collect(&self) -> impl Iterator<Item = (RunId, &BenchSuiteRun, Vec<&str>, PathBuf)> {
         self.runs.iter().filter_map(|(id, config)| {
             let tmp: HashSet<&str> = self
                 .collections
@@ -98,12 +276,273 @@ impl BenchSuiteTasks {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+struct BenchSuiteCollection {
+    #[serde(flatten)]
+    filter: BenchSuiteConfig,
+    #[serde(flatten)]
+    tables: TableSelection,
+    /// Fields to split the collection by, one sub-collection per distinct combination of values
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    split_by: Vec<String>,
+    /// Options the runs must have in one of their option strings, e.g. `-XX:+AlwaysPreTouch` or
+    /// `-Dspecjbb.group.count=2`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    jvm_options: Vec<String>,
+    /// The field values a sub-collection created by `split_by` is restricted to
+    #[serde(skip_deserializing, skip_serializing_if = "BTreeMap::is_empty")]
+    split_values: BTreeMap<String, Option<String>>,
+    /// The configuration fields whose value differs between the runs of the collection
+    #[serde(skip)]
+    varying_fields: Vec<String>,
+    /// Keys that are neither run fields nor collection options, likely mistyped filters
+    #[serde(flatten, skip_serializing)]
+    unknown: BTreeMap<String, serde_json::Value>,
+}
+
+impl BenchSuiteCollection {
+    fn has_jvm_options(&self, run: &BenchSuiteRun) -> bool {
+        if self.jvm_options.is_empty() {
+            return true;
+        }
+        let run_options: Vec<ConfigOption> = run
+            .config_options()
+            .into_iter()
+            .map(|(_, option)| option)
+            .collect();
+        self.jvm_options
+            .iter()
+            .flat_map(|options| parse_jvm_options(options))
+            .all(|option| run_options.contains(&option))
+    }
+
+    fn contains(&self, run: &BenchSuiteRun) -> bool {
+        self.filter.contains(run)
+            && self
+                .split_values
+                .iter()
+                .all(|(field, value)| run.field_string(field) == *value)
+            && self.has_jvm_options(run)
+    }
+}
+
+/// The configuration fields whose value is not the same in all of `runs`, sorted by name.
+fn varying_fields<'a>(runs: impl Iterator<Item = &'a BenchSuiteRun>) -> Vec<String> {
+    let values: Vec<BTreeMap<&str, String>> = runs
+        .map(|run| run.config_values().into_iter().collect())
+        .collect();
+    let names: BTreeSet<&str> = values.iter().flat_map(|v| v.keys().copied()).collect();
+    names
+        .into_iter()
+        .filter(|name| values.iter().any(|v| v.get(name) != values[0].get(name)))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Replaces every collection with a `split_by` by one collection per combination of the split
+/// fields' values among the runs it matches.
+fn expand_splits(
+    collections: HashMap<String, BenchSuiteCollection>,
+    runs: &HashMap<RunId, BenchSuiteRun>,
+) -> Result<HashMap<String, BenchSuiteCollection>> {
+    let mut expanded = HashMap::new();
+    for (name, collection) in collections {
+        if collection.split_by.is_empty() {
+            if expanded.insert(name.clone(), collection).is_some() {
+                bail!("Collection {name} collides with a collection created by split_by");
+            }
+            continue;
+        }
+
+        for field in &collection.split_by {
+            if !BenchSuiteRun::FIELDS.iter().any(|info| info.name == field) {
+                bail!("Collection {name} is split by unknown field {field}");
+            }
+        }
+
+        let combinations: BTreeSet<Vec<Option<String>>> = runs
+            .values()
+            .filter(|run| collection.contains(run))
+            .map(|run| {
+                collection
+                    .split_by
+                    .iter()
+                    .map(|field| run.field_string(field))
+                    .collect()
+            })
+            .collect();
+
+        for values in combinations {
+            let split_name = split_collection_name(&name, &collection.split_by, &values);
+            let split = BenchSuiteCollection {
+                filter: collection.filter.clone(),
+                tables: collection.tables.clone(),
+                split_by: Vec::new(),
+                jvm_options: collection.jvm_options.clone(),
+                split_values: collection.split_by.iter().cloned().zip(values).collect(),
+                varying_fields: Vec::new(),
+                unknown: collection.unknown.clone(),
+            };
+            if expanded.insert(split_name.clone(), split).is_some() {
+                bail!("Collection {split_name} is produced more than once by splitting {name}");
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+/// Names the sub-collection of `name` holding the runs with the given split field `values`.
+///
+/// A `{field}` placeholder in `name` is replaced by that field's value. Fields without a
+/// placeholder have their value appended, before the extension if `name` has one, so
+/// `"zgc.db"` split by `gc` becomes e.g. `"zgc_ZGC.db"`.
+fn split_collection_name(name: &str, split_by: &[String], values: &[Option<String>]) -> String {
+    let mut templated = name.to_string();
+    let mut suffix = String::new();
+    for (field, value) in split_by.iter().zip(values) {
+        let value = value
+            .as_deref()
+            .map_or_else(|| "none".to_string(), path_component);
+        let placeholder = format!("{{{field}}}");
+        if templated.contains(&placeholder) {
+            templated = templated.replace(&placeholder, &value);
+        } else {
+            suffix.push('_');
+            suffix.push_str(&value);
+        }
+    }
+    match templated.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && !extension.contains('/') => {
+            format!("{stem}{suffix}.{extension}")
+        }
+        _ => templated + &suffix,
+    }
+}
+
+/// Makes a field value usable as part of a directory name, e.g. a jdk path
+/// `/home/user/jdks/pqos/` becomes `home_user_jdks_pqos`.
+fn path_component(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string()
+}
+
+/// Reads the runs out of the `status.json` of a bench-suite result location.
+fn load_status(location: &Path) -> Result<HashMap<u64, BenchSuiteRun>> {
+    let status_location = location.join("status.json");
+    let status_reader = BufReader::new(File::open(&status_location).context(std::format!(
+        "Failed to open status file {}",
+        status_location.display()
+    ))?);
+    let status: BenchSuiteStatus = serde_json::from_reader(status_reader).context(std::format!(
+        "Failed to parse status file {}",
+        status_location.display()
+    ))?;
+
+    let BenchSuiteStatus {
+        bench_index: _,
+        benchmark_runs,
+    } = status;
+
+    let benchmark_runs: Result<HashMap<u64, BenchSuiteRun>, std::num::ParseIntError> =
+        benchmark_runs
+            .into_iter()
+            .map(|(key, val)| key.parse::<u64>().map(|parsed| (parsed, val)))
+            .collect();
+    benchmark_runs.context(std::format!("The runs in {}", status_location.display()))
+}
+
+/// The rule violations of the runs in `collections`. Runs outside every collection are not
+/// collected, so their rules do not matter.
+fn collected_rule_violations(
+    runs: &HashMap<RunId, BenchSuiteRun>,
+    collections: &HashMap<String, BenchSuiteCollection>,
+) -> Vec<(RunId, RuleViolation)> {
+    runs.iter()
+        .filter(|(_, run)| collections.values().any(|c| c.contains(run)))
+        .flat_map(|(id, run)| {
+            run.rule_violations()
+                .into_iter()
+                .map(move |violation| (*id, violation))
+        })
+        .collect()
+}
+
+/// The bench-suite result locations a task collects from.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Locations {
+    One(String),
+    Many(Vec<String>),
+    Named(HashMap<String, String>),
+}
+
+impl Locations {
+    /// Names every location. Unless given explicitly a location is named after its last path
+    /// component, e.g. `bench_suite7` for `/home/user/results/bench_suite7/`.
+    fn sources(&self) -> Result<HashMap<Intern, PathBuf>> {
+        let named: Vec<(String, &String)> = match self {
+            Locations::One(location) => vec![(source_name(location)?, location)],
+            Locations::Many(locations) => locations
+                .iter()
+                .map(|location| Ok((source_name(location)?, location)))
+                .collect::<Result<_>>()?,
+            Locations::Named(locations) => locations
+                .iter()
+                .map(|(name, location)| (name.clone(), location))
+                .collect(),
+        };
+
+        let mut sources = HashMap::new();
+        for (name, location) in named {
+            if sources
+                .insert(Intern::new(name.as_str()), PathBuf::from(location))
+                .is_some()
+            {
+                bail!("Several locations are named {name}, give them names explicitly");
+            }
+        }
+        Ok(sources)
+    }
+}
+
+fn source_name(location: &str) -> Result<String> {
+    Path::new(location)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+        .context(std::format!("Cannot name the location {location}"))
+}
+
 #[derive(Debug, Deserialize)]
 struct BenchSuiteTaskConfig {
-    location: String,
-    collect: HashMap<String, BenchSuiteConfig>,
+    location: Locations,
+    /// Where to write the collections, defaults to the location if there is only one
+    #[serde(default)]
+    output: Option<String>,
+    collect: HashMap<String, BenchSuiteCollection>,
     #[serde(default)]
     drop_tables: HashSet<Intern>,
+    /// Options of the collectors by collector name, e.g. `{"threadstat": {"drop_failed_reads": false}}`
+    #[serde(default)]
+    collector_options: HashMap<String, serde_json::Value>,
+    /// One-off tables extracted from the runs' files with a regex each, see
+    /// `bench_suite_collector_regex::RegexCollectorConfig`
+    #[serde(default)]
+    regex_collectors: Vec<serde_json::Value>,
+    /// Tables computed from each run's tables by a SQL query, see
+    /// `bench_suite_derive_sql::SqlQuery`
+    #[serde(default)]
+    sql: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -111,3 +550,25 @@ struct BenchSuiteStatus {
     bench_index: f64,
     benchmark_runs: HashMap<String, BenchSuiteRun>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_names_fill_placeholders_and_keep_extension() {
+        let split_by = vec!["gc".to_string(), "jdk".to_string()];
+        let values = vec![
+            Some("ZGC".to_string()),
+            Some("/home/user/jdks/pqos/".to_string()),
+        ];
+        assert_eq!(
+            split_collection_name("runs.db", &split_by, &values),
+            "runs_ZGC_home_user_jdks_pqos.db"
+        );
+        assert_eq!(
+            split_collection_name("{gc}/runs", &split_by, &[Some("G1".to_string()), None]),
+            "G1/runs_none"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Reading task files.
+//!
+//! Task files are JSON, TOML or YAML depending on their extension. A task file can `include`
+//! other task files, which are merged underneath it, and can use `${NAME}` in any string to refer
+//! to an entry of its `variables` table or, failing that, an environment variable. `$${` is a
+//! literal `${`, e.g. in a SQL query or regex.
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Reads the task file at `path` with its includes merged and its variables substituted.
+///
+/// # Errors
+///
+/// Returns `Err` if the file or one of its includes cannot be read or parsed, if the files
+/// include each other in a cycle, or if a variable is used but not defined.
+pub(crate) fn load(path: &Path) -> Result<Value> {
+    let mut value = load_with_includes(path, &mut Vec::new())?;
+    let variables = match &mut value {
+        Value::Object(map) => take_variables(map)?,
+        _ => HashMap::new(),
+    };
+    substitute(&mut value, &variables)?;
+    Ok(value)
+}
+
+fn parse(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to open task file {}", path.display()))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as TOML", path.display())),
+        Some("yaml" | "yml") => serde_yaml_ng::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as YAML", path.display())),
+        _ => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as JSON", path.display())),
+    }
+}
+
+fn load_with_includes(path: &Path, including: &mut Vec<PathBuf>) -> Result<Value> {
+    let canonical = fs::canonicalize(path)
+        .with_context(|| format!("Failed to open task file {}", path.display()))?;
+    if including.contains(&canonical) {
+        bail!("Task file {} includes itself", path.display());
+    }
+
+    let mut value = parse(path)?;
+    let Value::Object(map) = &mut value else {
+        bail!("Task file {} does not contain a table", path.display());
+    };
+    let includes = match map.remove("include") {
+        None => Vec::new(),
+        Some(Value::String(include)) => vec![include],
+        Some(Value::Array(includes)) => includes
+            .into_iter()
+            .map(|include| match include {
+                Value::String(include) => Ok(include),
+                other => bail!("Expected an include path, got {other}"),
+            })
+            .collect::<Result<_>>()?,
+        Some(other) => bail!("Expected an include path or a list of them, got {other}"),
+    };
+
+    including.push(canonical);
+    let directory = path.parent().unwrap_or(Path::new(""));
+    let mut merged = Value::Object(Map::new());
+    for include in includes {
+        merge(
+            &mut merged,
+            load_with_includes(&directory.join(include), including)?,
+        );
+    }
+    including.pop();
+
+    merge(&mut merged, value);
+    Ok(merged)
+}
+
+/// Merges `overlay` into `base`. Tables are merged key by key, anything else in `overlay`
+/// replaces what is in `base`.
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn take_variables(map: &mut Map<String, Value>) -> Result<HashMap<String, String>> {
+    let Some(variables) = map.remove("variables") else {
+        return Ok(HashMap::new());
+    };
+    let Value::Object(variables) = variables else {
+        bail!("Expected variables to be a table, got {variables}");
+    };
+    variables
+        .into_iter()
+        .map(|(name, value)| match value {
+            Value::String(value) => Ok((name, value)),
+            Value::Number(_) | Value::Bool(_) => Ok((name, value.to_string())),
+            other => bail!("Variable {name} must be a string, number or bool, got {other}"),
+        })
+        .collect()
+}
+
+/// Substitutes variables in every string and table key of `value`. Keys of a table that are
+/// the same after substitution are an error, as one would silently replace the other.
+fn substitute(value: &mut Value, variables: &HashMap<String, String>) -> Result<()> {
+    match value {
+        Value::String(string) => *string = substitute_str(string, variables)?,
+        Value::Array(values) => {
+            for value in values {
+                substitute(value, variables)?;
+            }
+        }
+        Value::Object(map) => {
+            let mut substituted = Map::new();
+            for (key, mut value) in std::mem::take(map) {
+                substitute(&mut value, variables)?;
+                let new_key = substitute_str(&key, variables)?;
+                if substituted.contains_key(&new_key) {
+                    bail!("Several keys are {new_key} once their variables are substituted");
+                }
+                substituted.insert(new_key, value);
+            }
+            *map = substituted;
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+fn substitute_str(string: &str, variables: &HashMap<String, String>) -> Result<String> {
+    let mut result = String::with_capacity(string.len());
+    let mut rest = string;
+    while let Some(start) = rest.find("${") {
+        if let Some(before) = rest[..start].strip_suffix('$') {
+            result.push_str(before);
+            result.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .with_context(|| format!("Unterminated variable in {string}"))?;
+        let name = &after[..end];
+        match variables.get(name) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(
+                &env::var(name).with_context(|| format!("Variable {name} is not defined"))?,
+            ),
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_keeps_included_collections_and_overrides_scalars() {
+        let mut base = json!({"location": "/old", "collect": {"a.db": {"gc": ["ZGC"]}}});
+        merge(
+            &mut base,
+            json!({"location": "/new", "collect": {"b.db": {"gc": ["G1"]}}}),
+        );
+        assert_eq!(
+            base,
+            json!({"location": "/new", "collect": {"a.db": {"gc": ["ZGC"]}, "b.db": {"gc": ["G1"]}}})
+        );
+    }
+
+    #[test]
+    fn substitutes_variables_in_values_and_keys() {
+        let variables = HashMap::from([
+            ("RESULTS".to_string(), "/results".to_string()),
+            ("NAME".to_string(), "zgc".to_string()),
+        ]);
+        let mut value =
+            json!({"location": "${RESULTS}/bench_suite7", "collect": {"${NAME}.db": {}}});
+        substitute(&mut value, &variables).unwrap();
+        assert_eq!(
+            value,
+            json!({"location": "/results/bench_suite7", "collect": {"zgc.db": {}}})
+        );
+        assert!(substitute_str("${BENCH_SUITE_UNDEFINED_VARIABLE}", &variables).is_err());
+
+        let mut colliding = json!({"collect": {"${NAME}.db": {}, "zgc.db": {}}});
+        assert!(substitute(&mut colliding, &variables).is_err());
+    }
+
+    #[test]
+    fn escaped_variables_are_kept_and_undefined_ones_come_from_the_environment() {
+        let variables = HashMap::from([("NAME".to_string(), "zgc".to_string())]);
+        assert_eq!(
+            substitute_str("$${NAME} ${NAME}", &variables).unwrap(),
+            "${NAME} zgc"
+        );
+        assert_eq!(
+            substitute_str("${PATH}", &variables).unwrap(),
+            env::var("PATH").unwrap()
+        );
+        // The variables table takes precedence over the environment
+        let variables = HashMap::from([("PATH".to_string(), "/bin".to_string())]);
+        assert_eq!(substitute_str("${PATH}", &variables).unwrap(), "/bin");
+    }
+}
@@ -0,0 +1,299 @@
+//! Checks of a task file against the runs it collects from.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Component, Path};
+
+use bench_suite_types::{BenchSuiteRun, BenchmarkKind, RuleViolation};
+
+use crate::BenchSuiteTasks;
+
+/// A likely mistake in a task file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The collection's filter matches no runs.
+    EmptyCollection { collection: String },
+    /// A key of a collection that is not a run field or collection option, so it filters nothing.
+    UnknownFilterField {
+        collection: String,
+        field: String,
+        /// The run field with the same name ignoring case, if there is one
+        suggestion: Option<&'static str>,
+    },
+    /// A filter value that no run has for that field.
+    UnknownFilterValue {
+        collection: String,
+        field: &'static str,
+        value: String,
+    },
+    /// Two collections that would be written to the same directory, or one inside the other.
+    CollidingCollections { first: String, second: String },
+    /// Runs that would be collected but whose benchmark has no collection config.
+    UnknownBenchmark { benchmark: String, runs: usize },
+    /// Runs that would be collected but whose gc is not a known collector, so no GC specific
+    /// tables are collected for them.
+    UnknownGc { gc: String, runs: usize },
+    /// Runs that break a rule of their benchmark, e.g. a dacapo run without `dacapo_benchmark`.
+    InvalidRuns {
+        benchmark: String,
+        violation: RuleViolation,
+        runs: usize,
+    },
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::EmptyCollection { collection } => {
+                write!(f, "Collection {collection} does not match any runs")
+            }
+            ValidationIssue::UnknownFilterField {
+                collection,
+                field,
+                suggestion,
+            } => {
+                write!(
+                    f,
+                    "Collection {collection} has key {field}, which is not a run field"
+                )?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean {suggestion}?"),
+                    None => Ok(()),
+                }
+            }
+            ValidationIssue::UnknownFilterValue {
+                collection,
+                field,
+                value,
+            } => write!(
+                f,
+                "Collection {collection} filters {field} on {value}, which no run has"
+            ),
+            ValidationIssue::CollidingCollections { first, second } => {
+                write!(f, "Collections {first} and {second} collide on disk")
+            }
+            ValidationIssue::UnknownBenchmark { benchmark, runs } => write!(
+                f,
+                "{runs} runs are of benchmark {benchmark}, which has no collection config"
+            ),
+            ValidationIssue::UnknownGc { gc, runs } => write!(
+                f,
+                "{runs} runs use gc {gc}, which is not a known collector so has no GC tables"
+            ),
+            ValidationIssue::InvalidRuns {
+                benchmark,
+                violation,
+                runs,
+            } => write!(f, "{runs} runs of benchmark {benchmark}: {violation}"),
+        }
+    }
+}
+
+impl BenchSuiteTasks {
+    /// Checks the task file against the runs in the status files.
+    ///
+    /// `known_benchmark` says whether a benchmark has a collection config.
+    #[must_use]
+    pub fn validate(
+        &self,
+        known_benchmark: impl Fn(&BenchmarkKind) -> bool,
+    ) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let mut names: Vec<&String> = self.collections.keys().collect();
+        names.sort();
+
+        for name in &names {
+            let collection = &self.collections[*name];
+            if !self.runs.values().any(|run| collection.contains(run)) {
+                issues.push(ValidationIssue::EmptyCollection {
+                    collection: (*name).clone(),
+                });
+            }
+            for field in collection.unknown.keys() {
+                issues.push(ValidationIssue::UnknownFilterField {
+                    collection: (*name).clone(),
+                    field: field.clone(),
+                    suggestion: BenchSuiteRun::FIELDS
+                        .iter()
+                        .find(|info| info.name.eq_ignore_ascii_case(field))
+                        .map(|info| info.name),
+                });
+            }
+            for (field, value) in collection.filter.unmatched_values(self.runs.values()) {
+                issues.push(ValidationIssue::UnknownFilterValue {
+                    collection: (*name).clone(),
+                    field,
+                    value,
+                });
+            }
+        }
+
+        let keys: Vec<Vec<String>> = names.iter().map(|name| disk_key(name)).collect();
+        for (i, first) in keys.iter().enumerate() {
+            for (j, second) in keys.iter().enumerate().skip(i + 1) {
+                if first.starts_with(second) || second.starts_with(first) {
+                    issues.push(ValidationIssue::CollidingCollections {
+                        first: names[i].clone(),
+                        second: names[j].clone(),
+                    });
+                }
+            }
+        }
+
+        let mut unknown_benchmarks: BTreeMap<String, usize> = BTreeMap::new();
+        let mut unknown_gcs: BTreeMap<String, usize> = BTreeMap::new();
+        for (_, run, _, _) in self.to_collect() {
+            if !known_benchmark(&run.benchmark) {
+                *unknown_benchmarks
+                    .entry(run.benchmark.to_string())
+                    .or_default() += 1;
+            }
+            if let Some(gc) = run.gc.filter(|gc| gc.kind().is_other()) {
+                *unknown_gcs.entry(gc.to_string()).or_default() += 1;
+            }
+        }
+        for (benchmark, runs) in unknown_benchmarks {
+            issues.push(ValidationIssue::UnknownBenchmark { benchmark, runs });
+        }
+        for (gc, runs) in unknown_gcs {
+            issues.push(ValidationIssue::UnknownGc { gc, runs });
+        }
+
+        let mut invalid_runs: BTreeMap<(String, &RuleViolation), usize> = BTreeMap::new();
+        for (id, violation) in &self.rule_violations {
+            let benchmark = self.runs[id].benchmark.to_string();
+            *invalid_runs.entry((benchmark, violation)).or_default() += 1;
+        }
+        for ((benchmark, violation), runs) in invalid_runs {
+            issues.push(ValidationIssue::InvalidRuns {
+                benchmark,
+                violation: violation.clone(),
+                runs,
+            });
+        }
+
+        issues
+    }
+}
+
+/// The directory a collection is written to, compared case insensitively so collections that
+/// collide on case insensitive file systems are caught too.
+fn disk_key(name: &str) -> Vec<String> {
+    Path::new(name)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    /// Tasks over the runs `runs`, given as `status.json` entries, with the collections
+    /// `collect` of a task file. The location and task file are written below a temporary
+    /// directory named after `test`, and loaded like any other task file.
+    fn tasks(
+        test: &str,
+        runs: &[serde_json::Value],
+        collect: serde_json::Value,
+    ) -> BenchSuiteTasks {
+        let dir =
+            std::env::temp_dir().join(format!("bench-suite-config-{test}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let runs: serde_json::Map<String, serde_json::Value> = runs
+            .iter()
+            .enumerate()
+            .map(|(id, run)| (id.to_string(), run.clone()))
+            .collect();
+        let status = json!({"bench_index": runs.len(), "benchmark_runs": runs});
+        fs::write(dir.join("status.json"), status.to_string()).unwrap();
+        let task_path = dir.join("task.json");
+        let task = json!({"location": dir, "collect": collect});
+        fs::write(&task_path, task.to_string()).unwrap();
+
+        let tasks = BenchSuiteTasks::new(&task_path);
+        fs::remove_dir_all(&dir).unwrap();
+        tasks.unwrap()
+    }
+
+    fn dacapo_run(gc: &str) -> serde_json::Value {
+        json!({
+            "benchmark": "dacapo_samples2",
+            "tar_file": "runs/0000000000000000.tar.xz",
+            "iteration": 0,
+            "gc": gc,
+            "dacapo_benchmark": "h2",
+        })
+    }
+
+    #[test]
+    fn filters_that_match_nothing_are_reported() {
+        let tasks = tasks(
+            "filters_that_match_nothing_are_reported",
+            &[dacapo_run("ZGC")],
+            json!({"g1": {"gc": ["G1"]}}),
+        );
+        assert_eq!(
+            tasks.validate(|_| true),
+            [
+                ValidationIssue::EmptyCollection {
+                    collection: "g1".to_string(),
+                },
+                ValidationIssue::UnknownFilterValue {
+                    collection: "g1".to_string(),
+                    field: "gc",
+                    value: "G1".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn collections_in_the_same_directory_collide() {
+        let tasks = tasks(
+            "collections_in_the_same_directory_collide",
+            &[dacapo_run("ZGC")],
+            json!({"zgc": {}, "ZGC/runs": {}, "zgc_runs": {}}),
+        );
+        assert_eq!(
+            tasks.validate(|_| true),
+            [ValidationIssue::CollidingCollections {
+                first: "ZGC/runs".to_string(),
+                second: "zgc".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn only_collected_runs_break_rules() {
+        let mut no_benchmark = dacapo_run("ZGC");
+        no_benchmark
+            .as_object_mut()
+            .unwrap()
+            .remove("dacapo_benchmark");
+        let mut uncollected = dacapo_run("G1");
+        uncollected
+            .as_object_mut()
+            .unwrap()
+            .remove("dacapo_benchmark");
+        let tasks = tasks(
+            "only_collected_runs_break_rules",
+            &[dacapo_run("ZGC"), no_benchmark, uncollected],
+            json!({"zgc": {"gc": ["ZGC"]}}),
+        );
+        assert_eq!(
+            tasks.validate(|_| true),
+            [ValidationIssue::InvalidRuns {
+                benchmark: "dacapo_samples2".to_string(),
+                violation: RuleViolation::MissingField("dacapo_benchmark"),
+                runs: 1,
+            }]
+        );
+    }
+}
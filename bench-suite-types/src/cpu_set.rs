@@ -0,0 +1,139 @@
+//! Sets of CPUs as given by CPU masks and CPU list strings.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A set of CPU indices, e.g. from `cpu_mask` or `GCThreadCPUs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CpuSet {
+    /// Sorted and without duplicates
+    cpus: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CpuSetError {
+    InvalidCpu(String),
+    InvalidRange(String),
+    InvalidMask(String),
+}
+
+impl Display for CpuSetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CpuSetError::InvalidCpu(v) => write!(f, "Expected a CPU number, got {v}"),
+            CpuSetError::InvalidRange(v) => write!(f, "Expected a CPU range like 0-3, got {v}"),
+            CpuSetError::InvalidMask(v) => write!(f, "Expected a hexadecimal CPU mask, got {v}"),
+        }
+    }
+}
+
+impl std::error::Error for CpuSetError {}
+
+impl CpuSet {
+    /// The CPUs whose bits are set in `mask`, CPU 0 being the lowest bit.
+    #[must_use]
+    pub fn from_mask(mask: u64) -> Self {
+        Self {
+            cpus: (0..u64::BITS)
+                .filter(|cpu| mask & (1 << cpu) != 0)
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cpus.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cpus.is_empty()
+    }
+
+    /// The CPUs in ascending order.
+    pub fn cpus(&self) -> impl Iterator<Item = u32> + '_ {
+        self.cpus.iter().copied()
+    }
+}
+
+impl FromStr for CpuSet {
+    type Err = CpuSetError;
+
+    /// Parses a CPU list like `0-3,8`, as taken by `taskset -c`, or a hexadecimal mask like
+    /// `0xff`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(mask) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            let mask =
+                u64::from_str_radix(mask, 16).map_err(|_| CpuSetError::InvalidMask(s.into()))?;
+            return Ok(Self::from_mask(mask));
+        }
+
+        let parse_cpu = |cpu: &str| {
+            cpu.trim()
+                .parse::<u32>()
+                .map_err(|_| CpuSetError::InvalidCpu(cpu.into()))
+        };
+        let mut cpus = Vec::new();
+        for part in s.split(',').filter(|part| !part.trim().is_empty()) {
+            match part.split_once('-') {
+                Some((first, last)) => {
+                    let (first, last) = (parse_cpu(first)?, parse_cpu(last)?);
+                    if first > last {
+                        return Err(CpuSetError::InvalidRange(part.into()));
+                    }
+                    cpus.extend(first..=last);
+                }
+                None => cpus.push(parse_cpu(part)?),
+            }
+        }
+        cpus.sort_unstable();
+        cpus.dedup();
+        Ok(Self { cpus })
+    }
+}
+
+impl Display for CpuSet {
+    /// Formats as a CPU list with consecutive CPUs merged into ranges, e.g. `0-3,8`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut cpus = self.cpus.iter().copied().peekable();
+        let mut first_range = true;
+        while let Some(first) = cpus.next() {
+            let mut last = first;
+            while cpus.next_if(|&cpu| cpu == last + 1).is_some() {
+                last += 1;
+            }
+            if !first_range {
+                write!(f, ",")?;
+            }
+            first_range = false;
+            if first == last {
+                write!(f, "{first}")?;
+            } else {
+                write!(f, "{first}-{last}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lists_and_masks() {
+        let set: CpuSet = "8,0-3, 2".parse().unwrap();
+        assert_eq!(set.cpus().collect::<Vec<_>>(), vec![0, 1, 2, 3, 8]);
+        assert_eq!(set.to_string(), "0-3,8");
+
+        let mask: CpuSet = "0xf0".parse().unwrap();
+        assert_eq!(mask, CpuSet::from_mask(0xf0));
+        assert_eq!(mask.to_string(), "4-7");
+
+        assert_eq!(
+            "3-1".parse::<CpuSet>(),
+            Err(CpuSetError::InvalidRange("3-1".into()))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{MapAccess, SeqAccess, Visitor},
+};
+
+/// The value of a run field that `BenchSuiteRun` has no typed field for yet.
+///
+/// Displays as the plain value for scalars, and as JSON for lists and maps.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtraValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    List(Vec<ExtraValue>),
+    Map(BTreeMap<String, ExtraValue>),
+}
+
+impl ExtraValue {
+    fn fmt_nested(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtraValue::String(s) => write!(f, "{s:?}"),
+            ExtraValue::List(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    value.fmt_nested(f)?;
+                }
+                write!(f, "]")
+            }
+            ExtraValue::Map(values) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{key:?}:")?;
+                    value.fmt_nested(f)?;
+                }
+                write!(f, "}}")
+            }
+            other => write!(f, "{other}"),
+        }
+    }
+}
+
+impl Display for ExtraValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtraValue::Null => write!(f, "null"),
+            ExtraValue::Bool(v) => write!(f, "{v}"),
+            ExtraValue::Int(v) => write!(f, "{v}"),
+            ExtraValue::UInt(v) => write!(f, "{v}"),
+            ExtraValue::Float(v) => write!(f, "{v}"),
+            ExtraValue::String(v) => write!(f, "{v}"),
+            ExtraValue::List(_) | ExtraValue::Map(_) => self.fmt_nested(f),
+        }
+    }
+}
+
+impl Hash for ExtraValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            ExtraValue::Null => {}
+            ExtraValue::Bool(v) => v.hash(state),
+            ExtraValue::Int(v) => v.hash(state),
+            ExtraValue::UInt(v) => v.hash(state),
+            ExtraValue::Float(v) => v.to_bits().hash(state),
+            ExtraValue::String(v) => v.hash(state),
+            ExtraValue::List(v) => v.hash(state),
+            ExtraValue::Map(v) => v.hash(state),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ExtraValueVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for ExtraValueVisitor {
+    type Value = ExtraValue;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::Int(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::UInt(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::Float(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<ExtraValue, E> {
+        Ok(ExtraValue::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<ExtraValue, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ExtraValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<ExtraValue, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(ExtraValue::List(values))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<ExtraValue, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut values = BTreeMap::new();
+        while let Some((key, value)) = map.next_entry()? {
+            values.insert(key, value);
+        }
+        Ok(ExtraValue::Map(values))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ExtraValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ExtraValueVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ExtraValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ExtraValue::Null => serializer.serialize_unit(),
+            ExtraValue::Bool(v) => serializer.serialize_bool(*v),
+            ExtraValue::Int(v) => serializer.serialize_i64(*v),
+            ExtraValue::UInt(v) => serializer.serialize_u64(*v),
+            ExtraValue::Float(v) => serializer.serialize_f64(*v),
+            ExtraValue::String(v) => serializer.serialize_str(v),
+            ExtraValue::List(v) => v.serialize(serializer),
+            ExtraValue::Map(v) => v.serialize(serializer),
+        }
+    }
+}
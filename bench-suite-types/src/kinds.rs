@@ -0,0 +1,200 @@
+//! Typed versions of the `gc` and `benchmark` run fields.
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use string_intern::Intern;
+
+/// The kind of garbage collector a run used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GcKind {
+    Zgc,
+    G1,
+    Parallel,
+    Serial,
+    Shenandoah,
+    Epsilon,
+    Other(Intern),
+}
+
+impl GcKind {
+    /// Whether the gc is not one of the known collectors.
+    #[must_use]
+    pub fn is_other(&self) -> bool {
+        matches!(self, GcKind::Other(_))
+    }
+}
+
+impl From<&str> for GcKind {
+    fn from(value: &str) -> Self {
+        let lower = value.trim().to_ascii_lowercase();
+        let name = lower.strip_prefix("-xx:+").unwrap_or(&lower);
+        let name = name.strip_prefix("use").unwrap_or(name);
+        let name = name.strip_suffix("gc").unwrap_or(name);
+        match name {
+            "z" => GcKind::Zgc,
+            "g1" => GcKind::G1,
+            "parallel" => GcKind::Parallel,
+            "serial" => GcKind::Serial,
+            "shenandoah" => GcKind::Shenandoah,
+            "epsilon" => GcKind::Epsilon,
+            // Spellings like GenZGC, which the collectors have always accepted
+            _ if lower.contains("zgc") => GcKind::Zgc,
+            _ if lower.contains("g1") => GcKind::G1,
+            _ => GcKind::Other(Intern::new(value)),
+        }
+    }
+}
+
+impl Display for GcKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GcKind::Zgc => "ZGC",
+            GcKind::G1 => "G1",
+            GcKind::Parallel => "Parallel",
+            GcKind::Serial => "Serial",
+            GcKind::Shenandoah => "Shenandoah",
+            GcKind::Epsilon => "Epsilon",
+            GcKind::Other(name) => name.as_str(),
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The garbage collector of a run, as it is spelled in `status.json`.
+///
+/// Runs are compared, hashed and displayed by their spelling, so e.g. `GenZGC` and `ZGC` runs
+/// stay apart, while `kind` parses the spelling leniently for the collectors deciding whether
+/// a run's logs are theirs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gc {
+    kind: GcKind,
+    spelling: Intern,
+}
+
+impl Gc {
+    #[must_use]
+    pub fn kind(&self) -> GcKind {
+        self.kind
+    }
+
+    /// The gc as it is written in `status.json`.
+    #[must_use]
+    pub fn spelling(&self) -> &str {
+        self.spelling.as_str()
+    }
+}
+
+impl From<&str> for Gc {
+    fn from(value: &str) -> Self {
+        Self {
+            kind: GcKind::from(value),
+            spelling: Intern::new(value),
+        }
+    }
+}
+
+impl Display for Gc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.spelling())
+    }
+}
+
+/// The benchmark a run executed, which decides the collectors run on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BenchmarkKind {
+    DacapoSamples2,
+    MarkAbuse,
+    Specjbb,
+    Other(Intern),
+}
+
+impl From<&str> for BenchmarkKind {
+    fn from(value: &str) -> Self {
+        match value {
+            "dacapo_samples2" => BenchmarkKind::DacapoSamples2,
+            "mark_abuse" => BenchmarkKind::MarkAbuse,
+            "specjbb" => BenchmarkKind::Specjbb,
+            _ => BenchmarkKind::Other(Intern::new(value)),
+        }
+    }
+}
+
+impl Display for BenchmarkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BenchmarkKind::DacapoSamples2 => "dacapo_samples2",
+            BenchmarkKind::MarkAbuse => "mark_abuse",
+            BenchmarkKind::Specjbb => "specjbb",
+            BenchmarkKind::Other(name) => name.as_str(),
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Gc {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(String::deserialize(deserializer)?.as_str()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Gc {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.spelling())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for BenchmarkKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::from(String::deserialize(deserializer)?.as_str()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for BenchmarkKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gc_spellings_are_normalized() {
+        for spelling in ["ZGC", "zgc", "UseZGC", "-XX:+UseZGC"] {
+            assert_eq!(GcKind::from(spelling), GcKind::Zgc);
+        }
+        assert_eq!(GcKind::from("G1GC"), GcKind::G1);
+        assert_eq!(GcKind::from("-XX:+UseShenandoahGC"), GcKind::Shenandoah);
+        assert_eq!(
+            GcKind::from("ZGenerational"),
+            GcKind::Other(Intern::new("ZGenerational"))
+        );
+        assert_eq!(GcKind::from("GenZGC"), GcKind::Zgc);
+        assert_eq!(GcKind::from("zgc").to_string(), "ZGC");
+
+        let gc = Gc::from("-XX:+UseZGC");
+        assert_eq!(gc.kind(), Gc::from("ZGC").kind());
+        assert_ne!(Gc::from("GenZGC"), Gc::from("ZGC"));
+        assert_eq!(gc.to_string(), "-XX:+UseZGC");
+        assert_eq!(gc.spelling(), "-XX:+UseZGC");
+    }
+}
@@ -3,32 +3,99 @@ use custom_float::PositiveNonZeroF64;
 #[cfg(feature = "polars")]
 use polars::prelude::*;
 #[cfg(feature = "serde")]
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "polars")]
+use std::collections::BTreeSet;
+use std::fmt::Display;
 use string_intern::Intern;
 
+mod cpu_set;
+mod extra;
+mod kinds;
+mod options;
+mod rules;
+mod units;
+pub use cpu_set::{CpuSet, CpuSetError};
+pub use extra::ExtraValue;
+pub use kinds::{BenchmarkKind, Gc, GcKind};
+pub use options::{
+    ConfigOption, OptionKind, parse_arguments, parse_classpath, parse_jvm_options, parse_properties,
+};
+pub use rules::{BenchmarkRules, RuleViolation};
+pub use units::{ByteSize, DurationSecs, UnitError};
+
 #[cfg(feature = "polars")]
 mod polars_support;
 #[cfg(feature = "polars")]
 use polars_support::ToSeriesColumn;
 
+/// The part of bench-suite a run field configures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldCategory {
+    General,
+    Java,
+    Dacapo,
+    Specjbb,
+    MarkAbuse,
+    Threadstat,
+    Cos,
+}
+
+impl Display for FieldCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FieldCategory::General => "general",
+            FieldCategory::Java => "java",
+            FieldCategory::Dacapo => "dacapo",
+            FieldCategory::Specjbb => "specjbb",
+            FieldCategory::MarkAbuse => "mark_abuse",
+            FieldCategory::Threadstat => "threadstat",
+            FieldCategory::Cos => "cos",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Static description of a run field, as declared in `make_vectorized!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    /// The Rust type of the field, without the `Option` of optional fields
+    pub type_name: &'static str,
+    pub optional: bool,
+    pub category: FieldCategory,
+}
+
 macro_rules! make_vectorized {
     ($original:ident, $vectorized:ident ,  { $($field:ident : $typ:ty),* $(,)? },
-     optional:{$($opt_field:ident : $opt_typ:ty),* $(,)?}) => {
+     optional:{$($category:ident : { $($opt_field:ident : $opt_typ:ty),* $(,)? }),* $(,)?}) => {
         #[allow(non_snake_case)]
-        #[cfg_attr(feature = "serde", derive(Deserialize))]
+        #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
         #[derive(Debug, Clone, PartialEq,Hash)]
-        #[serde(deny_unknown_fields)]
         pub struct $original {
             $(pub $field: $typ),*,
-            $(pub $opt_field: Option<$opt_typ>),*
+            $($(
+                #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+                pub $opt_field: Option<$opt_typ>,
+            )*)*
+            /// Fields of the run that have no typed field yet, e.g. options newly added to bench-suite
+            #[cfg_attr(feature = "serde", serde(flatten))]
+            pub extra: BTreeMap<String, ExtraValue>,
         }
 
         #[allow(non_snake_case)]
-        #[cfg_attr(feature = "serde", derive(Deserialize))]
+        #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
         #[derive(Debug, Clone)]
         pub struct $vectorized {
-            $($field: Option<Vec<$typ>>),*,
-            $($opt_field: Option<Vec<$opt_typ>>),*,
+            $(
+                #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+                $field: Option<Vec<$typ>>
+            ),*,
+            $($(
+                #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+                $opt_field: Option<Vec<$opt_typ>>,
+            )*)*
         }
 
         impl $vectorized {
@@ -43,7 +110,7 @@ macro_rules! make_vectorized {
                         return false;
                     }
                 )*
-                $(
+                $($(
                      if ! match &self.$opt_field{
                         Some(v)=>{
                             match &item.$opt_field{
@@ -58,10 +125,72 @@ macro_rules! make_vectorized {
                     }{
                         return false;
                     }
-                )*
+                )*)*
                 true
             }
 
+            /// Returns the field name and value of every value in this filter that none of
+            /// `runs` have, e.g. a mistyped jdk path.
+            #[must_use]
+            pub fn unmatched_values<'a>(
+                &self,
+                runs: impl Iterator<Item = &'a $original> + Clone,
+            ) -> Vec<(&'static str, String)> {
+                let mut unmatched = Vec::new();
+                $(
+                    if let Some(values) = &self.$field {
+                        for value in values {
+                            if !runs.clone().any(|run| run.$field == *value) {
+                                unmatched.push((stringify!($field), value.to_string()));
+                            }
+                        }
+                    }
+                )*
+                $($(
+                    if let Some(values) = &self.$opt_field {
+                        for value in values {
+                            if !runs.clone().any(|run| run.$opt_field.as_ref() == Some(value)) {
+                                unmatched.push((stringify!($opt_field), value.to_string()));
+                            }
+                        }
+                    }
+                )*)*
+                unmatched
+            }
+        }
+
+        impl $original {
+            /// Every field with its type and category, in declaration order.
+            pub const FIELDS: &'static [FieldInfo] = &[
+                $(
+                    FieldInfo {
+                        name: stringify!($field),
+                        type_name: stringify!($typ),
+                        optional: false,
+                        category: FieldCategory::General,
+                    },
+                )*
+                $($(
+                    FieldInfo {
+                        name: stringify!($opt_field),
+                        type_name: stringify!($opt_typ),
+                        optional: true,
+                        category: FieldCategory::$category,
+                    },
+                )*)*
+            ];
+
+            /// Returns the value of the field called `field` formatted as a string.
+            ///
+            /// Returns `None` if the field is an unset optional or there is no such field.
+            #[must_use]
+            pub fn field_string(&self, field: &str) -> Option<String> {
+                match field {
+                    $(stringify!($field) => Some(self.$field.to_string()),)*
+                    $($(stringify!($opt_field) => self.$opt_field.as_ref().map(ToString::to_string),)*)*
+                    _ => None,
+                }
+            }
         }
 
         #[cfg(feature="polars")]
@@ -72,101 +201,447 @@ macro_rules! make_vectorized {
             ///
             /// Returns `Err` if Polars fails to construct the `DataFrame` from the column series.
             pub fn to_df(&self)->Result<DataFrame,polars::error::PolarsError>{
-                let columns: Vec<Column> = vec![
-                    $(
-                        self.$field.to_series_column(stringify!($field).into()).into(),
-                    )*
+                Self::to_df_many(core::iter::once(self))
+            }
+
+            /// Converts many run configs into one Polars `DataFrame` with a row per run.
+            ///
+            /// Every field that is unknown to `BenchSuiteRun` in any of `runs` becomes a string
+            /// column, null for the runs without it.
+            ///
+            /// # Errors
+            ///
+            /// Returns `Err` if Polars fails to construct the `DataFrame` from the column series.
+            pub fn to_df_many<'a>(
+                runs: impl Iterator<Item = &'a Self> + Clone,
+            )->Result<DataFrame,polars::error::PolarsError>{
+                let mut columns: Vec<Column> = vec![
                     $(
-                        self.$opt_field.to_series_column(stringify!($opt_field).into()).into(),
+                        <$typ>::column_from_options(
+                            stringify!($field).into(),
+                            runs.clone().map(|run| Some(&run.$field)),
+                        ).into(),
                     )*
+                    $($(
+                        <$opt_typ>::column_from_options(
+                            stringify!($opt_field).into(),
+                            runs.clone().map(|run| run.$opt_field.as_ref()),
+                        ).into(),
+                    )*)*
                 ];
-                DataFrame::new(1,columns)
+                // The typed columns are declared by the field types, so they cannot drift apart
+                debug_assert!(
+                    Self::config_schema()
+                        .iter()
+                        .zip(&columns)
+                        .all(|((name, dtype), column)| {
+                            column.name() == name && column.dtype() == dtype
+                        }),
+                    "the config columns do not match config_schema",
+                );
+                let extra_names: BTreeSet<&str> = runs
+                    .clone()
+                    .flat_map(|run| run.extra.keys().map(String::as_str))
+                    .collect();
+                for name in extra_names {
+                    columns.push(
+                        ExtraValue::column_from_options(
+                            name.into(),
+                            runs.clone().map(|run| run.extra.get(name)),
+                        )
+                        .into(),
+                    );
+                }
+                DataFrame::new(runs.count(),columns)
+            }
+
+            /// The schema of the typed columns of `to_df`, without the columns of unknown fields.
+            #[must_use]
+            pub fn config_schema() -> Schema {
+                Schema::from_iter([
+                    $(Field::new(stringify!($field).into(), <$typ>::dtype()),)*
+                    $($(Field::new(stringify!($opt_field).into(), <$opt_typ>::dtype()),)*)*
+                ])
             }
         }
     };
 }
 
 make_vectorized!(BenchSuiteRun,BenchSuiteConfig,{
-    benchmark:Intern,
+    benchmark:BenchmarkKind,
     tar_file:String,
     iteration:u64,
 } , optional:{
-    timeout:NonZero<u64>,
-    cpu_mask:NonZero<u64>,
-    //java
-    jdk:Intern,
-    process_count:NonZero<u64>,
-    gc:Intern,
-    classpath:Intern,
-
-    gc_logging:Intern,
-    java_log_gc:Intern,
-    java_log_os:Intern,
-    memory_ratio:PositiveNonZeroF64,
-    memory_config:NonZero<u64>,
-    softmax:Intern,
-    softmax_ratio:PositiveNonZeroF64,
-    concgcthreads:NonZero<u64>,
-    jdk_tiered_compilation:bool,
-    zgc_barrier_use_global_variable:bool,
-    zgc_barrier_rewrite_on_phase_change:bool,
-
-    GCThreadCPUs:Intern,
-    NonGCThreadCPUs:Intern,
-
-    zgc_deactivate_proactive:bool,
-
-    opp_zgc:bool,
-    opp_zgc_period:NonZero<u64>,
-    opp_zgc_minor_threshold:u64,
-    opp_zgc_major_threshold:u64,
-    opp_zgc_avg_cpu_window:NonZero<u64>,
-
-    java_thp:bool,
-
-    ResctrlIdleGCMask:NonZero<u64>,
-    ResctrlMarkingGCMask:NonZero<u64>,
-    ResctrlCollectingGCMask:NonZero<u64>,
-
-    ResctrlIdleAppMask:NonZero<u64>,
-    ResctrlMarkingAppMask:NonZero<u64>,
-    ResctrlCollectingAppMask:NonZero<u64>,
-
-
-    //dacapo
-    dacapo_benchmark:Intern,
-    dacapo_location:Intern,
-    dacapo_threads:NonZero<u64>,
-    dacapo_harness:Intern,
-
-    //specjbb
-    specjbb_location:Intern,
-    specjbb_props:Intern,
-    specjbb_opts:Intern,
-    specjbb_args:Intern,
-    specjbb_report_level:u8,
-
-    //mark abuse
-    mark_abuse_location:Intern,
-    mark_abuse_cardinality:NonZero<u64>,
-    mark_abuse_keys:NonZero<u64>,
-    mark_abuse_iterations:NonZero<u64>,
-    mark_abuse_warmup:NonZero<u64>,
-    mark_abuse_graph_nodes:NonZero<u64>,
-    mark_abuse_edges_per_node:NonZero<u64>,
-    mark_abuse_rotate_interval:u64,
-    mark_abuse_rotate_fraction:PositiveNonZeroF64,
-
-
-    //threadstat
-    threadstat_location:Intern,
-    threadstat_wrapper_location:Intern,
-    threadstat_event:Intern,
-    threadstat_frequency:NonZero<u64>,
-
-    //cos
-    cos_config:Intern,
-    cache_ways:NonZero<u64>,
+    General: {
+        timeout:DurationSecs,
+        cpu_mask:NonZero<u64>,
+    },
+    Java: {
+        jdk:Intern,
+        process_count:NonZero<u64>,
+        gc:Gc,
+        classpath:Intern,
 
+        gc_logging:Intern,
+        java_log_gc:Intern,
+        java_log_os:Intern,
+        memory_ratio:PositiveNonZeroF64,
+        memory_config:ByteSize,
+        softmax:Intern,
+        softmax_ratio:PositiveNonZeroF64,
+        concgcthreads:NonZero<u64>,
+        jdk_tiered_compilation:bool,
+        zgc_barrier_use_global_variable:bool,
+        zgc_barrier_rewrite_on_phase_change:bool,
 
+        GCThreadCPUs:Intern,
+        NonGCThreadCPUs:Intern,
+
+        zgc_deactivate_proactive:bool,
+
+        opp_zgc:bool,
+        opp_zgc_period:DurationSecs,
+        opp_zgc_minor_threshold:u64,
+        opp_zgc_major_threshold:u64,
+        opp_zgc_avg_cpu_window:NonZero<u64>,
+
+        java_thp:bool,
+
+        ResctrlIdleGCMask:NonZero<u64>,
+        ResctrlMarkingGCMask:NonZero<u64>,
+        ResctrlCollectingGCMask:NonZero<u64>,
+
+        ResctrlIdleAppMask:NonZero<u64>,
+        ResctrlMarkingAppMask:NonZero<u64>,
+        ResctrlCollectingAppMask:NonZero<u64>,
+    },
+    Dacapo: {
+        dacapo_benchmark:Intern,
+        dacapo_location:Intern,
+        dacapo_threads:NonZero<u64>,
+        dacapo_harness:Intern,
+    },
+    Specjbb: {
+        specjbb_location:Intern,
+        specjbb_props:Intern,
+        specjbb_opts:Intern,
+        specjbb_args:Intern,
+        specjbb_report_level:u8,
+    },
+    MarkAbuse: {
+        mark_abuse_location:Intern,
+        mark_abuse_cardinality:NonZero<u64>,
+        mark_abuse_keys:NonZero<u64>,
+        mark_abuse_iterations:NonZero<u64>,
+        mark_abuse_warmup:NonZero<u64>,
+        mark_abuse_graph_nodes:NonZero<u64>,
+        mark_abuse_edges_per_node:NonZero<u64>,
+        mark_abuse_rotate_interval:u64,
+        mark_abuse_rotate_fraction:PositiveNonZeroF64,
+    },
+    Threadstat: {
+        threadstat_location:Intern,
+        threadstat_wrapper_location:Intern,
+        threadstat_event:Intern,
+        threadstat_frequency:NonZero<u64>,
+    },
+    Cos: {
+        cos_config:Intern,
+        cache_ways:NonZero<u64>,
+    },
 });
+
+/// Fields that differ between repetitions of the same configuration.
+const REPETITION_FIELDS: &[&str] = &["iteration", "tar_file"];
+
+impl BenchSuiteRun {
+    /// The name and value of every set field that is part of the configuration, i.e. everything
+    /// but `iteration` and `tar_file`, sorted by name. Includes fields unknown to `BenchSuiteRun`.
+    #[must_use]
+    pub fn config_values(&self) -> Vec<(&str, String)> {
+        let mut values: Vec<(&str, String)> = Self::FIELDS
+            .iter()
+            .filter(|field| !REPETITION_FIELDS.contains(&field.name))
+            .filter_map(|field| Some((field.name, self.field_string(field.name)?)))
+            .chain(
+                self.extra
+                    .iter()
+                    .filter(|(_, value)| **value != ExtraValue::Null)
+                    .map(|(name, value)| (name.as_str(), value.to_string())),
+            )
+            .collect();
+        values.sort();
+        values
+    }
+
+    /// A hash of `config_values`, equal for runs that are repetitions of the same configuration.
+    ///
+    /// The hash is FNV-1a so it is stable across builds and can be compared between collections.
+    /// It does not depend on field order, and an unset optional field hashes the same as a field
+    /// `BenchSuiteRun` does not have.
+    #[must_use]
+    pub fn config_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for (name, value) in self.config_values() {
+            // Separate name and value so e.g. a=bc and ab=c hash differently
+            for byte in name.bytes().chain([0]).chain(value.bytes()).chain([0]) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+        hash
+    }
+
+    /// The CPUs the benchmark is pinned to by `cpu_mask`.
+    #[must_use]
+    pub fn cpu_set(&self) -> Option<CpuSet> {
+        self.cpu_mask.map(|mask| CpuSet::from_mask(mask.get()))
+    }
+
+    /// The CPUs of the GC threads, parsed from `GCThreadCPUs`. `Err` if it is neither a CPU list
+    /// nor a hexadecimal mask.
+    #[must_use]
+    pub fn gc_thread_cpus(&self) -> Option<Result<CpuSet, CpuSetError>> {
+        self.GCThreadCPUs.map(|cpus| cpus.as_str().parse())
+    }
+
+    /// The CPUs of the non GC threads, parsed from `NonGCThreadCPUs`. `Err` if it is neither a
+    /// CPU list nor a hexadecimal mask.
+    #[must_use]
+    pub fn non_gc_thread_cpus(&self) -> Option<Result<CpuSet, CpuSetError>> {
+        self.NonGCThreadCPUs.map(|cpus| cpus.as_str().parse())
+    }
+
+    /// The options in the run's option strings, with the name of the field each came from.
+    #[must_use]
+    pub fn config_options(&self) -> Vec<(&'static str, ConfigOption)> {
+        let parsers: [(&'static str, Option<Intern>, fn(&str) -> Vec<ConfigOption>); 4] = [
+            ("specjbb_opts", self.specjbb_opts, parse_jvm_options),
+            ("specjbb_props", self.specjbb_props, parse_properties),
+            ("specjbb_args", self.specjbb_args, parse_arguments),
+            ("classpath", self.classpath, parse_classpath),
+        ];
+        parsers
+            .into_iter()
+            .filter_map(|(field, value, parse)| Some((field, parse(value?.as_str()))))
+            .flat_map(|(field, options)| options.into_iter().map(move |option| (field, option)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "polars")]
+impl BenchSuiteRun {
+    /// The `config` table of `runs`: the columns of `to_df_many`, the `config_hash`, and columns
+    /// derived from the CPU and cache way masks.
+    ///
+    /// Each CPU set gets a `_count` and a `_list` column, e.g. `GCThreadCPUs_count` and
+    /// `GCThreadCPUs_list`, and each `Resctrl*Mask` gets a `_ways` column with its number of
+    /// cache ways. CPU strings that do not parse give nulls.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if Polars fails to construct the `DataFrame` from the column series.
+    pub fn to_config_df<'a>(
+        runs: impl Iterator<Item = &'a Self> + Clone,
+    ) -> Result<DataFrame, PolarsError> {
+        let mut df = Self::to_df_many(runs.clone())?;
+        df.with_column(Column::new(
+            "config_hash".into(),
+            runs.clone().map(Self::config_hash).collect::<Vec<u64>>(),
+        ))?;
+
+        let cpu_sets: [(&str, fn(&Self) -> Option<CpuSet>); 3] = [
+            ("cpu_mask", Self::cpu_set),
+            ("GCThreadCPUs", |run| run.gc_thread_cpus()?.ok()),
+            ("NonGCThreadCPUs", |run| run.non_gc_thread_cpus()?.ok()),
+        ];
+        for (name, cpu_set) in cpu_sets {
+            let sets: Vec<Option<CpuSet>> = runs.clone().map(cpu_set).collect();
+            df.with_column(Column::new(
+                format!("{name}_count").into(),
+                sets.iter()
+                    .map(|set| set.as_ref().map(|set| set.len() as u64))
+                    .collect::<Vec<Option<u64>>>(),
+            ))?;
+            df.with_column(Column::new(
+                format!("{name}_list").into(),
+                sets.iter()
+                    .map(|set| set.as_ref().map(ToString::to_string))
+                    .collect::<Vec<Option<String>>>(),
+            ))?;
+        }
+
+        let way_masks: [(&str, fn(&Self) -> Option<NonZero<u64>>); 6] = [
+            ("ResctrlIdleGCMask", |run| run.ResctrlIdleGCMask),
+            ("ResctrlMarkingGCMask", |run| run.ResctrlMarkingGCMask),
+            ("ResctrlCollectingGCMask", |run| run.ResctrlCollectingGCMask),
+            ("ResctrlIdleAppMask", |run| run.ResctrlIdleAppMask),
+            ("ResctrlMarkingAppMask", |run| run.ResctrlMarkingAppMask),
+            ("ResctrlCollectingAppMask", |run| {
+                run.ResctrlCollectingAppMask
+            }),
+        ];
+        for (name, mask) in way_masks {
+            df.with_column(Column::new(
+                format!("{name}_ways").into(),
+                runs.clone()
+                    .map(|run| mask(run).map(|mask| mask.get().count_ones()))
+                    .collect::<Vec<Option<u32>>>(),
+            ))?;
+        }
+        Ok(df)
+    }
+
+    /// The `config_options` table of the run: one row per option in `config_options`, with the
+    /// `field` it came from, its `kind`, `key` and `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if Polars fails to construct the `DataFrame`.
+    pub fn config_options_df(&self) -> Result<DataFrame, PolarsError> {
+        let options = self.config_options();
+        let fields: Vec<&str> = options.iter().map(|(field, _)| *field).collect();
+        let kinds: Vec<String> = options.iter().map(|(_, o)| o.kind.to_string()).collect();
+        let keys: Vec<&str> = options.iter().map(|(_, o)| o.key.as_str()).collect();
+        let values: Vec<Option<&str>> = options.iter().map(|(_, o)| o.value.as_deref()).collect();
+        df![
+            "field" => fields,
+            "kind" => kinds,
+            "key" => keys,
+            "value" => values,
+        ]
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    // Run entries as bench-suite writes them to status.json
+    const STATUS_RUNS: &str = r#"{
+        "0": {
+            "benchmark": "dacapo_samples2",
+            "tar_file": "runs/0000000000000000.tar.xz",
+            "iteration": 0,
+            "timeout": 3600,
+            "cpu_mask": 255,
+            "jdk": "/home/user/jdks/pqos/",
+            "gc": "ZGC",
+            "gc_logging": "gc*,gc+stats",
+            "java_log_gc": "gc*=info",
+            "memory_ratio": 2.5,
+            "concgcthreads": 2,
+            "jdk_tiered_compilation": true,
+            "GCThreadCPUs": "0-1",
+            "NonGCThreadCPUs": "2-7",
+            "opp_zgc": false,
+            "opp_zgc_minor_threshold": 0,
+            "ResctrlIdleGCMask": 3,
+            "dacapo_benchmark": "h2",
+            "dacapo_location": "/home/user/dacapo/dacapo-23.11-chopin.jar",
+            "dacapo_threads": 8,
+            "threadstat_frequency": 100,
+            "zgc_young_tenuring": 4,
+            "perf_events": ["cycles", "instructions"]
+        },
+        "1": {
+            "benchmark": "specjbb",
+            "tar_file": "runs/0000000000000001.tar.xz",
+            "iteration": 3,
+            "jdk": "/home/user/jdks/baseline/",
+            "gc": "G1",
+            "memory_config": 17179869184,
+            "specjbb_location": "/home/user/specjbb/",
+            "specjbb_props": "specjbb.controller.type=PRESET specjbb.controller.preset.ir=10000",
+            "specjbb_opts": "-Xms16g -Xmx16g -XX:+AlwaysPreTouch",
+            "specjbb_report_level": 2,
+            "cos_config": "{\"ways\": 4}"
+        },
+        "2": {
+            "benchmark": "mark_abuse",
+            "tar_file": "runs/0000000000000002.tar.xz",
+            "iteration": 1,
+            "gc": "UseZGC",
+            "softmax_ratio": 0.75,
+            "mark_abuse_iterations": 20,
+            "mark_abuse_rotate_interval": 0,
+            "mark_abuse_rotate_fraction": 0.125
+        }
+    }"#;
+
+    #[test]
+    fn runs_round_trip_through_json() {
+        let entries: BTreeMap<String, serde_json::Value> =
+            serde_json::from_str(STATUS_RUNS).unwrap();
+        for (id, entry) in entries {
+            let run: BenchSuiteRun = serde_json::from_value(entry.clone()).unwrap();
+            let serialized = serde_json::to_value(&run).unwrap();
+            assert_eq!(serialized, entry, "run {id} changed when serialized");
+            assert_eq!(
+                serde_json::from_value::<BenchSuiteRun>(serialized).unwrap(),
+                run
+            );
+        }
+    }
+
+    #[cfg(feature = "polars")]
+    #[test]
+    fn config_df_starts_with_the_config_schema() {
+        let entries: BTreeMap<String, BenchSuiteRun> = serde_json::from_str(STATUS_RUNS).unwrap();
+        let df = BenchSuiteRun::to_df_many(entries.values()).unwrap();
+        let schema = BenchSuiteRun::config_schema();
+        assert_eq!(df.height(), entries.len());
+        for ((name, dtype), (column, actual)) in schema.iter().zip(df.schema().iter()) {
+            assert_eq!((name, dtype), (column, actual));
+        }
+        // Followed by the fields unknown to BenchSuiteRun, as strings
+        assert!(
+            df.schema()
+                .iter()
+                .skip(schema.len())
+                .all(|(_, dtype)| *dtype == DataType::String)
+        );
+    }
+
+    #[test]
+    fn config_hash_ignores_repetition_fields() {
+        let entries: BTreeMap<String, BenchSuiteRun> = serde_json::from_str(STATUS_RUNS).unwrap();
+        let run = &entries["0"];
+
+        let mut repetition = run.clone();
+        repetition.iteration = 7;
+        repetition.tar_file = "runs/0000000000000007.tar.xz".to_string();
+        assert_eq!(repetition.config_hash(), run.config_hash());
+
+        let mut other_gc = run.clone();
+        other_gc.gc = Some(Gc::from("G1"));
+        assert_ne!(other_gc.config_hash(), run.config_hash());
+        // Generational ZGC is a configuration of its own, not a spelling of ZGC
+        other_gc.gc = Some(Gc::from("GenZGC"));
+        assert_ne!(other_gc.config_hash(), run.config_hash());
+        assert!(
+            !serde_json::from_value::<BenchSuiteConfig>(serde_json::json!({"gc": ["ZGC"]}))
+                .unwrap()
+                .contains(&other_gc)
+        );
+
+        let hashes: BTreeSet<u64> = entries.values().map(BenchSuiteRun::config_hash).collect();
+        assert_eq!(hashes.len(), entries.len());
+    }
+
+    #[test]
+    fn config_round_trips_through_json() {
+        let filter = serde_json::json!({
+            "benchmark": ["dacapo_samples2"],
+            "gc": ["ZGC", "G1"],
+            "memory_ratio": [2.5, 3.0],
+            "opp_zgc": [true],
+        });
+        let config: BenchSuiteConfig = serde_json::from_value(filter.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&config).unwrap(), filter);
+    }
+}
@@ -0,0 +1,197 @@
+//! Parsers for the option strings of a run: `specjbb_opts`, `specjbb_props`, `specjbb_args`
+//! and `classpath`.
+
+use std::fmt::Display;
+
+/// What kind of option a `ConfigOption` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionKind {
+    /// `-XX:+Flag`, `-XX:-Flag` or `-XX:Name=value`
+    Advanced,
+    /// `-Xmx16g` and other `-X` options
+    NonStandard,
+    /// `-Dname=value`, or `name=value` in a property list
+    Property,
+    /// Any other argument, with the value following it if there is one
+    Argument,
+    /// One entry of a classpath
+    ClasspathEntry,
+}
+
+impl Display for OptionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            OptionKind::Advanced => "advanced",
+            OptionKind::NonStandard => "non_standard",
+            OptionKind::Property => "property",
+            OptionKind::Argument => "argument",
+            OptionKind::ClasspathEntry => "classpath_entry",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// One option parsed out of an option string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConfigOption {
+    pub kind: OptionKind,
+    pub key: String,
+    pub value: Option<String>,
+}
+
+impl ConfigOption {
+    fn new(kind: OptionKind, key: &str, value: Option<&str>) -> Self {
+        Self {
+            kind,
+            key: key.to_string(),
+            value: value.map(str::to_string),
+        }
+    }
+}
+
+/// The `-X` options that take their value without a separator, e.g. `-Xmx16g`.
+const SIZE_OPTIONS: &[&str] = &["Xmx", "Xms", "Xmn", "Xss"];
+
+/// Parses one JVM option, e.g. `-XX:+UseLargePages` gives key `UseLargePages` and value `true`.
+fn parse_jvm_option(option: &str) -> ConfigOption {
+    if let Some(flag) = option.strip_prefix("-XX:") {
+        if let Some(name) = flag.strip_prefix('+') {
+            ConfigOption::new(OptionKind::Advanced, name, Some("true"))
+        } else if let Some(name) = flag.strip_prefix('-') {
+            ConfigOption::new(OptionKind::Advanced, name, Some("false"))
+        } else {
+            match flag.split_once('=') {
+                Some((name, value)) => ConfigOption::new(OptionKind::Advanced, name, Some(value)),
+                None => ConfigOption::new(OptionKind::Advanced, flag, None),
+            }
+        }
+    } else if let Some(property) = option.strip_prefix("-D") {
+        parse_property(property)
+    } else if let Some(name) = option
+        .strip_prefix('-')
+        .filter(|name| name.starts_with('X'))
+    {
+        match SIZE_OPTIONS.iter().find(|size| name.starts_with(*size)) {
+            Some(size) => {
+                ConfigOption::new(OptionKind::NonStandard, size, Some(&name[size.len()..]))
+            }
+            None => match name.split_once(':') {
+                Some((name, value)) => {
+                    ConfigOption::new(OptionKind::NonStandard, name, Some(value))
+                }
+                None => ConfigOption::new(OptionKind::NonStandard, name, None),
+            },
+        }
+    } else {
+        ConfigOption::new(OptionKind::Argument, option, None)
+    }
+}
+
+fn parse_property(property: &str) -> ConfigOption {
+    match property.split_once('=') {
+        Some((name, value)) => ConfigOption::new(OptionKind::Property, name, Some(value)),
+        None => ConfigOption::new(OptionKind::Property, property, None),
+    }
+}
+
+/// Parses whitespace separated JVM options, as in `specjbb_opts`.
+#[must_use]
+pub fn parse_jvm_options(options: &str) -> Vec<ConfigOption> {
+    options.split_whitespace().map(parse_jvm_option).collect()
+}
+
+/// Parses whitespace separated `name=value` properties, as in `specjbb_props`. A leading `-D` is
+/// allowed.
+#[must_use]
+pub fn parse_properties(properties: &str) -> Vec<ConfigOption> {
+    properties
+        .split_whitespace()
+        .map(|property| parse_property(property.strip_prefix("-D").unwrap_or(property)))
+        .collect()
+}
+
+/// Parses program arguments, as in `specjbb_args`. An argument starting with `-` takes the
+/// following argument as its value unless that starts with `-` too.
+#[must_use]
+pub fn parse_arguments(arguments: &str) -> Vec<ConfigOption> {
+    let mut options = Vec::new();
+    let mut arguments = arguments.split_whitespace().peekable();
+    while let Some(argument) = arguments.next() {
+        let value = if argument.starts_with('-') {
+            arguments.next_if(|value| !value.starts_with('-'))
+        } else {
+            None
+        };
+        options.push(ConfigOption::new(OptionKind::Argument, argument, value));
+    }
+    options
+}
+
+/// Splits a `:` separated classpath into its entries.
+#[must_use]
+pub fn parse_classpath(classpath: &str) -> Vec<ConfigOption> {
+    classpath
+        .split(':')
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| ConfigOption::new(OptionKind::ClasspathEntry, entry, None))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(kind: OptionKind, key: &str, value: Option<&str>) -> ConfigOption {
+        ConfigOption::new(kind, key, value)
+    }
+
+    #[test]
+    fn parses_jvm_options() {
+        assert_eq!(
+            parse_jvm_options(
+                "-Xms16g -Xmx16g -XX:+AlwaysPreTouch -XX:-UseCompressedOops \
+                 -XX:ParallelGCThreads=4 -Dspecjbb.group.count=2 -Xlog:gc -server"
+            ),
+            vec![
+                option(OptionKind::NonStandard, "Xms", Some("16g")),
+                option(OptionKind::NonStandard, "Xmx", Some("16g")),
+                option(OptionKind::Advanced, "AlwaysPreTouch", Some("true")),
+                option(OptionKind::Advanced, "UseCompressedOops", Some("false")),
+                option(OptionKind::Advanced, "ParallelGCThreads", Some("4")),
+                option(OptionKind::Property, "specjbb.group.count", Some("2")),
+                option(OptionKind::NonStandard, "Xlog", Some("gc")),
+                option(OptionKind::Argument, "-server", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_properties_arguments_and_classpath() {
+        assert_eq!(
+            parse_properties("specjbb.controller.type=PRESET -Dspecjbb.run.ir=1000"),
+            vec![
+                option(
+                    OptionKind::Property,
+                    "specjbb.controller.type",
+                    Some("PRESET")
+                ),
+                option(OptionKind::Property, "specjbb.run.ir", Some("1000")),
+            ]
+        );
+        assert_eq!(
+            parse_arguments("-m COMPOSITE -skipReport -p config/specjbb2015.props"),
+            vec![
+                option(OptionKind::Argument, "-m", Some("COMPOSITE")),
+                option(OptionKind::Argument, "-skipReport", None),
+                option(OptionKind::Argument, "-p", Some("config/specjbb2015.props")),
+            ]
+        );
+        assert_eq!(
+            parse_classpath("/opt/a.jar::/opt/lib/"),
+            vec![
+                option(OptionKind::ClasspathEntry, "/opt/a.jar", None),
+                option(OptionKind::ClasspathEntry, "/opt/lib/", None),
+            ]
+        );
+    }
+}
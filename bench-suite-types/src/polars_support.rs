@@ -1,96 +1,192 @@
 use core::num::NonZero;
-use core::slice;
 use custom_float::PositiveNonZeroF64;
 use polars::prelude::*;
 use string_intern::Intern;
 
-// Trait to convert a single value to a Series column
+use crate::{BenchmarkKind, ByteSize, DurationSecs, ExtraValue, Gc};
+
+// Trait to build a Series column from the values of one field across many runs
 // This abstracts over different types so the macro can use a uniform interface
 pub trait ToSeriesColumn {
-    fn to_series_column(&self, name: PlSmallStr) -> Series;
-    fn get_null(name: PlSmallStr) -> Series;
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series
+    where
+        Self: 'a;
+    fn dtype() -> DataType;
 }
 
 impl ToSeriesColumn for Intern {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        StringChunked::from_slice(name, &[self.as_str()]).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        StringChunked::from_iter_options(name, values.map(|v| v.map(Intern::as_str))).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::String)
+    fn dtype() -> DataType {
+        DataType::String
     }
 }
 
 impl ToSeriesColumn for String {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        StringChunked::from_slice(name, &[self.as_str()]).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        StringChunked::from_iter_options(name, values.map(|v| v.map(String::as_str))).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::String)
+    fn dtype() -> DataType {
+        DataType::String
     }
 }
 
 impl ToSeriesColumn for u64 {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        UInt64Chunked::from_slice(name, slice::from_ref(self)).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        UInt64Chunked::from_iter_options(name, values.map(|v| v.copied())).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::UInt64)
+    fn dtype() -> DataType {
+        DataType::UInt64
     }
 }
 
 impl ToSeriesColumn for u8 {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        UInt8Chunked::from_slice(name, slice::from_ref(self)).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        UInt8Chunked::from_iter_options(name, values.map(|v| v.copied())).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::UInt8)
+    fn dtype() -> DataType {
+        DataType::UInt8
     }
 }
 
 impl ToSeriesColumn for f64 {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        Float64Chunked::from_slice(name, slice::from_ref(self)).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        Float64Chunked::from_iter_options(name, values.map(|v| v.copied())).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::Float64)
+    fn dtype() -> DataType {
+        DataType::Float64
     }
 }
 
 impl ToSeriesColumn for bool {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        BooleanChunked::from_slice(name, slice::from_ref(self)).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        BooleanChunked::from_iter_options(name, values.map(|v| v.copied())).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::Boolean)
+    fn dtype() -> DataType {
+        DataType::Boolean
     }
 }
 
 impl ToSeriesColumn for PositiveNonZeroF64 {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        Float64Chunked::from_slice(name, slice::from_ref(&self.get())).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        Float64Chunked::from_iter_options(name, values.map(|v| v.map(|v| v.get()))).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::Float64)
+    fn dtype() -> DataType {
+        DataType::Float64
     }
 }
 
 impl ToSeriesColumn for NonZero<u64> {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        UInt64Chunked::from_slice(name, slice::from_ref(&self.get())).into_series()
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        UInt64Chunked::from_iter_options(name, values.map(|v| v.map(|v| v.get()))).into_series()
+    }
+    fn dtype() -> DataType {
+        DataType::UInt64
+    }
+}
+
+// Unknown fields can hold different kinds of values in different runs, so they are always
+// strings to keep the config table's schema consistent
+impl ToSeriesColumn for ExtraValue {
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        StringChunked::from_iter_options(
+            name,
+            values.map(|v| match v {
+                None | Some(ExtraValue::Null) => None,
+                Some(value) => Some(value.to_string()),
+            }),
+        )
+        .into_series()
+    }
+    fn dtype() -> DataType {
+        DataType::String
+    }
+}
+
+impl ToSeriesColumn for Gc {
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        StringChunked::from_iter_options(name, values.map(|v| v.map(Gc::spelling))).into_series()
+    }
+    fn dtype() -> DataType {
+        DataType::String
+    }
+}
+
+impl ToSeriesColumn for BenchmarkKind {
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        StringChunked::from_iter_options(name, values.map(|v| v.map(ToString::to_string)))
+            .into_series()
+    }
+    fn dtype() -> DataType {
+        DataType::String
+    }
+}
+
+// Sizes are written in bytes
+impl ToSeriesColumn for ByteSize {
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        UInt64Chunked::from_iter_options(name, values.map(|v| v.map(|v| v.bytes()))).into_series()
     }
-    fn get_null(name: PlSmallStr) -> Series {
-        Series::full_null(name, 1, &DataType::UInt64)
+    fn dtype() -> DataType {
+        DataType::UInt64
     }
 }
 
-impl<T: ToSeriesColumn> ToSeriesColumn for Option<T> {
-    fn to_series_column(&self, name: PlSmallStr) -> Series {
-        match self {
-            Some(v) => v.to_series_column(name),
-            None => T::get_null(name),
-        }
-    }
-    fn get_null(name: PlSmallStr) -> Series {
-        T::get_null(name)
+impl ToSeriesColumn for DurationSecs {
+    fn column_from_options<'a>(
+        name: PlSmallStr,
+        values: impl Iterator<Item = Option<&'a Self>>,
+    ) -> Series {
+        Int64Chunked::from_iter_options(
+            name,
+            values.map(|v| {
+                v.map(|v| i64::try_from(v.secs().saturating_mul(1000)).unwrap_or(i64::MAX))
+            }),
+        )
+        .into_duration(TimeUnit::Milliseconds)
+        .into_series()
+    }
+    fn dtype() -> DataType {
+        DataType::Duration(TimeUnit::Milliseconds)
     }
 }
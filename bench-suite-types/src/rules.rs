@@ -0,0 +1,96 @@
+//! Which run fields each benchmark needs, and which it cannot have.
+
+use std::fmt::Display;
+
+use crate::{BenchSuiteRun, BenchmarkKind, FieldCategory};
+
+/// The fields a run of a benchmark must set, and the categories of fields it must not set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchmarkRules {
+    pub required: &'static [&'static str],
+    pub forbidden: &'static [FieldCategory],
+}
+
+/// A run that breaks the rules of its benchmark.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RuleViolation {
+    MissingField(&'static str),
+    ForbiddenField(&'static str),
+}
+
+impl Display for RuleViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleViolation::MissingField(field) => write!(f, "{field} is not set"),
+            RuleViolation::ForbiddenField(field) => {
+                write!(f, "{field} is set but is not used by the benchmark")
+            }
+        }
+    }
+}
+
+impl BenchmarkKind {
+    #[must_use]
+    pub fn rules(&self) -> BenchmarkRules {
+        match self {
+            BenchmarkKind::DacapoSamples2 => BenchmarkRules {
+                required: &["dacapo_benchmark"],
+                forbidden: &[FieldCategory::Specjbb, FieldCategory::MarkAbuse],
+            },
+            BenchmarkKind::MarkAbuse => BenchmarkRules {
+                required: &["mark_abuse_iterations"],
+                forbidden: &[FieldCategory::Dacapo, FieldCategory::Specjbb],
+            },
+            BenchmarkKind::Specjbb => BenchmarkRules {
+                required: &["specjbb_location"],
+                forbidden: &[FieldCategory::Dacapo, FieldCategory::MarkAbuse],
+            },
+            BenchmarkKind::Other(_) => BenchmarkRules {
+                required: &[],
+                forbidden: &[],
+            },
+        }
+    }
+}
+
+impl BenchSuiteRun {
+    /// Checks the run against the rules of its benchmark.
+    #[must_use]
+    pub fn rule_violations(&self) -> Vec<RuleViolation> {
+        let rules = self.benchmark.rules();
+        let missing = Self::FIELDS
+            .iter()
+            .filter(|field| rules.required.contains(&field.name))
+            .filter(|field| self.field_string(field.name).is_none())
+            .map(|field| RuleViolation::MissingField(field.name));
+        let forbidden = Self::FIELDS
+            .iter()
+            .filter(|field| rules.forbidden.contains(&field.category))
+            .filter(|field| self.field_string(field.name).is_some())
+            .map(|field| RuleViolation::ForbiddenField(field.name));
+        missing.chain(forbidden).collect()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dacapo_runs_need_a_benchmark_and_no_specjbb_fields() {
+        let run: BenchSuiteRun = serde_json::from_value(serde_json::json!({
+            "benchmark": "dacapo_samples2",
+            "tar_file": "runs/0000000000000000.tar.xz",
+            "iteration": 0,
+            "specjbb_report_level": 2,
+        }))
+        .unwrap();
+        assert_eq!(
+            run.rule_violations(),
+            vec![
+                RuleViolation::MissingField("dacapo_benchmark"),
+                RuleViolation::ForbiddenField("specjbb_report_level"),
+            ]
+        );
+    }
+}
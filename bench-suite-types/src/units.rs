@@ -0,0 +1,264 @@
+//! Run fields with units: sizes in bytes and durations in seconds.
+
+use core::num::NonZero;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as DeError};
+
+/// A non-zero size in bytes.
+///
+/// Parsed from a number of bytes or a string with a binary unit suffix like the JVM's, e.g.
+/// `"16g"`, `"512m"` or `"64KiB"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ByteSize(NonZero<u64>);
+
+/// A non-zero duration in whole seconds.
+///
+/// Parsed from a number of seconds or a string of numbers with units, e.g. `"90s"`, `"2h"` or
+/// `"1h30m"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DurationSecs(NonZero<u64>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnitError {
+    Zero,
+    Overflow(String),
+    Invalid(String),
+}
+
+impl Display for UnitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnitError::Zero => write!(f, "Expected a NonZero value"),
+            UnitError::Overflow(v) => write!(f, "{v} does not fit in 64 bits"),
+            UnitError::Invalid(v) => write!(f, "Expected a number with an optional unit, got {v}"),
+        }
+    }
+}
+
+impl std::error::Error for UnitError {}
+
+const BYTE_UNITS: &[(&str, u64)] = &[
+    ("t", 1 << 40),
+    ("g", 1 << 30),
+    ("m", 1 << 20),
+    ("k", 1 << 10),
+    ("", 1),
+];
+
+const DURATION_UNITS: &[(&str, u64)] = &[("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+
+/// Formats `value` with the largest unit that divides it exactly.
+fn fmt_with_unit(
+    f: &mut std::fmt::Formatter<'_>,
+    value: u64,
+    units: &[(&str, u64)],
+) -> std::fmt::Result {
+    match units.iter().find(|(_, size)| value % size == 0) {
+        Some((unit, size)) => write!(f, "{}{unit}", value / size),
+        None => write!(f, "{value}"),
+    }
+}
+
+fn non_zero(value: u64) -> Result<NonZero<u64>, UnitError> {
+    NonZero::new(value).ok_or(UnitError::Zero)
+}
+
+impl ByteSize {
+    /// Creates a new `ByteSize` of `bytes` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnitError::Zero` if `bytes` is zero.
+    pub fn new(bytes: u64) -> Result<Self, UnitError> {
+        non_zero(bytes).map(Self)
+    }
+
+    #[must_use]
+    pub fn bytes(self) -> u64 {
+        self.0.get()
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mib(self) -> f64 {
+        self.bytes() as f64 / f64::from(1 << 20)
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn gib(self) -> f64 {
+        self.bytes() as f64 / f64::from(1 << 30)
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = UnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let number = lower
+            .strip_suffix("ib")
+            .or_else(|| lower.strip_suffix('b'))
+            .unwrap_or(&lower);
+        let (unit, size) = BYTE_UNITS
+            .iter()
+            .find(|(unit, _)| number.ends_with(unit))
+            .ok_or_else(|| UnitError::Invalid(s.into()))?;
+        let count: u64 = number[..number.len() - unit.len()]
+            .trim()
+            .parse()
+            .map_err(|_| UnitError::Invalid(s.into()))?;
+        let bytes = count
+            .checked_mul(*size)
+            .ok_or_else(|| UnitError::Overflow(s.into()))?;
+        Self::new(bytes)
+    }
+}
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_with_unit(f, self.bytes(), BYTE_UNITS)
+    }
+}
+
+impl DurationSecs {
+    /// Creates a new `DurationSecs` of `secs` seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnitError::Zero` if `secs` is zero.
+    pub fn new(secs: u64) -> Result<Self, UnitError> {
+        non_zero(secs).map(Self)
+    }
+
+    #[must_use]
+    pub fn secs(self) -> u64 {
+        self.0.get()
+    }
+
+    #[must_use]
+    pub fn duration(self) -> Duration {
+        Duration::from_secs(self.secs())
+    }
+}
+
+impl FromStr for DurationSecs {
+    type Err = UnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if let Ok(secs) = trimmed.parse() {
+            return Self::new(secs);
+        }
+
+        let invalid = || UnitError::Invalid(s.into());
+        let overflow = || UnitError::Overflow(s.into());
+        let mut secs: u64 = 0;
+        let mut rest = trimmed.to_ascii_lowercase();
+        while !rest.is_empty() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(invalid)?;
+            let count: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+            let (unit, size) = DURATION_UNITS
+                .iter()
+                .find(|(unit, _)| rest[digits..].starts_with(unit))
+                .ok_or_else(invalid)?;
+            secs = count
+                .checked_mul(*size)
+                .and_then(|part| secs.checked_add(part))
+                .ok_or_else(overflow)?;
+            rest = rest[digits + unit.len()..].trim_start().to_string();
+        }
+        Self::new(secs)
+    }
+}
+
+impl Display for DurationSecs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_with_unit(f, self.secs(), DURATION_UNITS)
+    }
+}
+
+/// A number in the base unit, or a string with units.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(u64),
+    String(String),
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match NumberOrString::deserialize(deserializer)? {
+            NumberOrString::Number(bytes) => Self::new(bytes),
+            NumberOrString::String(size) => size.parse(),
+        }
+        .map_err(DeError::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ByteSize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DurationSecs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match NumberOrString::deserialize(deserializer)? {
+            NumberOrString::Number(secs) => Self::new(secs),
+            NumberOrString::String(duration) => duration.parse(),
+        }
+        .map_err(DeError::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for DurationSecs {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.secs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes_and_durations_with_units() {
+        let size: ByteSize = "16g".parse().unwrap();
+        assert_eq!(size.bytes(), 16 << 30);
+        assert_eq!(size.to_string(), "16g");
+        assert_eq!("64KiB".parse::<ByteSize>().unwrap().bytes(), 64 << 10);
+        assert_eq!("1000".parse::<ByteSize>().unwrap().to_string(), "1000");
+        assert_eq!("0m".parse::<ByteSize>(), Err(UnitError::Zero));
+
+        let duration: DurationSecs = "1h30m".parse().unwrap();
+        assert_eq!(duration.secs(), 5_400);
+        assert_eq!(duration.to_string(), "90m");
+        assert_eq!("2h".parse::<DurationSecs>().unwrap().secs(), 7_200);
+        assert_eq!("90".parse::<DurationSecs>().unwrap().to_string(), "90s");
+        assert!("2 weeks".parse::<DurationSecs>().is_err());
+    }
+}
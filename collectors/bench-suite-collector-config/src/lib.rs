@@ -24,9 +24,31 @@ impl BenchSuiteCollect for BenchSuiteCollectConfig {
     }
 
     fn get_result(self: Box<Self>, config: &BenchSuiteRun) -> Result<Vec<(Intern, LazyFrame)>> {
-        Ok(vec![(
-            Intern::from_static("config"),
-            config.to_df()?.lazy(),
-        )])
+        Ok(vec![
+            (
+                Intern::from_static("config"),
+                BenchSuiteRun::to_config_df(std::iter::once(config))?.lazy(),
+            ),
+            (
+                Intern::from_static("config_options"),
+                config.config_options_df()?.lazy(),
+            ),
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "config"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["config", "config_options"]
     }
 }
@@ -8,6 +8,13 @@ use string_intern::Intern;
 static ITERATION_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"DaCapo.*in (\d+) msec").unwrap());
 
+static ITERATION_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::from_iter(vec![
+        Field::new("dacapo_iteration".into(), DataType::UInt64),
+        Field::new("dacapo_iteration_time_ms".into(), DataType::UInt64),
+    ])
+});
+
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectDacapoIteration {
     iteration_df: Option<DataFrame>,
@@ -63,11 +70,40 @@ impl BenchSuiteCollect for BenchSuiteCollectDacapoIteration {
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
         let mut rv = Vec::new();
         if let Some(df) = self.iteration_df {
-            let lf = df.lazy().with_column(
-                col("dacapo_iteration_time_ms").cast(DataType::Duration(TimeUnit::Milliseconds)),
-            );
+            let lf = transform_iterations(df);
             rv.push((Intern::from_static("dacapo_iteration"), lf));
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "dacapo_iteration"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["jvm0.stdout", "jvm0.txt"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["dacapo_iteration"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        match table {
+            "dacapo_iteration" => {
+                bench_suite_collect_results::derive_schema(&ITERATION_SCHEMA, transform_iterations)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn transform_iterations(df: DataFrame) -> LazyFrame {
+    df.lazy().with_column(
+        col("dacapo_iteration_time_ms").cast(DataType::Duration(TimeUnit::Milliseconds)),
+    )
 }
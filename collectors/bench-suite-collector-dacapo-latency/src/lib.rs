@@ -101,4 +101,20 @@ impl BenchSuiteCollect for BenchSuiteCollectDacapoLatency {
             })
             .collect())
     }
+
+    fn name(&self) -> &'static str {
+        "dacapo_latency"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["dacapo-latency-usec-*.csv"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["dacapo_latency_*"]
+    }
 }
@@ -1,26 +1,50 @@
 use anyhow::Context;
-use bench_suite_collect_results::BenchSuiteCollect;
+use bench_suite_collect_results::{BenchSuiteCollect, LogOptions};
+use bench_suite_types::GcKind;
 use polars::prelude::*;
 use regex::Regex;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 use string_intern::Intern;
+use unified_log::{Level, LogLine};
 
-static GC_PHASE_TIMES_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"\[([^\]]*)\]\[info\s*\]\[gc,phases\s*\] GC\((\d+)\)\s+([A-Za-z ]+):\s+([0-9.]+)ms")
-        .unwrap()
-});
+static GC_PHASE_TIMES_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^GC\((\d+)\)\s+([A-Za-z ]+):\s+([0-9.]+)ms").unwrap());
 
+// Start-of-cycle lines such as `GC(5) Concurrent Mark Cycle` end right after the type and are
+// deliberately not matched, so the Remark/Cleanup pauses inside the cycle keep their own type.
 static GC_TYPE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r"\[info\s*\]\[gc\s*\] GC\((\d+)\) (Pause Young \([^)]+\)|Pause \w+|Concurrent [A-Za-z ]+?)(?:\s+\(G1|\s+\d|\s*$)",
+        r"^GC\((\d+)\) (Pause Young \([^)]+\)|Pause \w+|Concurrent [A-Za-z ]+?)(?:\s+\(G1|\s+\d)",
     )
     .unwrap()
 });
 
+static PHASES_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::from_iter(vec![
+        Field::new("clock_time".into(), DataType::String),
+        Field::new("uptime".into(), DataType::Int64),
+        Field::new("gc_number".into(), DataType::UInt32),
+        Field::new("gc_type".into(), DataType::String),
+        Field::new("name".into(), DataType::String),
+        Field::new("time_ms".into(), DataType::Float64),
+    ])
+});
+
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectG1Phases {
-    phases_df: Option<DataFrame>,
+    options: LogOptions,
+    /// The gc log the rows were read from
+    log_file: Option<String>,
+    /// gc_number -> gc_type from the [gc] summary lines, which follow the phases of their GC
+    gc_types: HashMap<u32, String>,
+    clock_times: Vec<Option<String>>,
+    /// Microseconds since the JVM started, if the log has an uptime decoration
+    uptimes: Vec<Option<i64>>,
+    gc_numbers: Vec<u32>,
+    phase_names: Vec<String>,
+    phase_times_ms: Vec<f64>,
 }
 
 impl BenchSuiteCollectG1Phases {
@@ -31,34 +55,40 @@ impl BenchSuiteCollectG1Phases {
 }
 
 impl BenchSuiteCollect for BenchSuiteCollectG1Phases {
-    fn process_file(
+    fn wants_lines(&self, run: &bench_suite_types::BenchSuiteRun, _: &str) -> bool {
+        run.gc.is_some_and(|gc| gc.kind() == GcKind::G1)
+    }
+
+    fn start_lines(
         &mut self,
-        run: &bench_suite_types::BenchSuiteRun,
-        file: &mut dyn bench_suite_collect_results::FileInfoInterface,
+        _: &bench_suite_types::BenchSuiteRun,
+        file_name: &str,
     ) -> anyhow::Result<()> {
-        match &run.gc {
-            Some(gc) if gc.as_str().to_lowercase().contains("g1") => {}
-            _ => return Ok(()),
+        if self.log_file.replace(file_name.to_string()).is_some() {
+            return Err(anyhow::anyhow!("Duplicate gc log files"));
         }
+        Ok(())
+    }
 
-        let name = file.name();
-        if name != "gc.javalog"
-            && name != "jvm0.txt" // LEGACY: remove once all tests use split files
-        {
-            return Ok(());
-        }
+    fn log_tags(&self) -> &'static [&'static str] {
+        &["gc", "gc,phases"]
+    }
 
-        if self.phases_df.is_some() {
-            return Err(anyhow::anyhow!("Duplicate gc log files"));
+    fn process_log_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &LogLine,
+    ) -> anyhow::Result<()> {
+        if !line.is_level(Level::Info) {
+            return Ok(());
         }
 
-        let content = file.content_string()?;
-
-        // Build gc_number -> gc_type map from [gc] summary lines.
         // Use or_insert so that for gc_numbers with multiple [gc] lines (e.g. concurrent
         // mark cycle with embedded Remark/Cleanup pauses), we keep the first entry.
-        let mut gc_types: HashMap<u32, String> = HashMap::new();
-        for cap in GC_TYPE_REGEX.captures_iter(content) {
+        if line.has_tags("gc")
+            && let Some(cap) = GC_TYPE_REGEX.captures(line.message)
+        {
             let gc_number: u32 = cap
                 .get(1)
                 .context("Missing GC number")?
@@ -71,50 +101,40 @@ impl BenchSuiteCollect for BenchSuiteCollectG1Phases {
                 .as_str()
                 .trim()
                 .to_string();
-            gc_types.entry(gc_number).or_insert(gc_type);
+            self.gc_types.entry(gc_number).or_insert(gc_type);
+            return Ok(());
         }
 
-        let mut clock_times: Vec<String> = Vec::new();
-        let mut gc_numbers: Vec<u32> = Vec::new();
-        let mut gc_type_col: Vec<String> = Vec::new();
-        let mut phase_names: Vec<String> = Vec::new();
-        let mut phase_times_ms: Vec<f64> = Vec::new();
-
-        for cap in GC_PHASE_TIMES_REGEX.captures_iter(content) {
-            let clock_time = cap.get(1).context("Missing clock time")?.as_str();
-            let gc_number: u32 = cap
-                .get(2)
-                .context("Missing GC number")?
-                .as_str()
-                .parse()
-                .context("Failed to parse GC number")?;
-            let phase_name = cap.get(3).context("Missing phase name")?.as_str().trim();
-            let phase_time: f64 = cap
-                .get(4)
-                .context("Missing phase time")?
-                .as_str()
-                .parse()
-                .context("Failed to parse phase time")?;
-
-            let gc_type = gc_types.get(&gc_number).cloned().unwrap_or_default();
-
-            clock_times.push(clock_time.to_string());
-            gc_numbers.push(gc_number);
-            gc_type_col.push(gc_type);
-            phase_names.push(phase_name.to_string());
-            phase_times_ms.push(phase_time);
+        if !line.has_tags("gc,phases") {
+            return Ok(());
         }
-
-        let df = df![
-            "clock_time" => clock_times,
-            "gc_number" => gc_numbers,
-            "gc_type" => gc_type_col,
-            "name" => phase_names,
-            "time_ms" => phase_times_ms,
-        ]
-        .context("Failed to create phases DataFrame")?;
-
-        self.phases_df = Some(df);
+        let Some(cap) = GC_PHASE_TIMES_REGEX.captures(line.message) else {
+            return Ok(());
+        };
+        let clock_time = line.decorations.time;
+        let gc_number: u32 = cap
+            .get(1)
+            .context("Missing GC number")?
+            .as_str()
+            .parse()
+            .context("Failed to parse GC number")?;
+        let phase_name = cap.get(2).context("Missing phase name")?.as_str().trim();
+        let phase_time: f64 = cap
+            .get(3)
+            .context("Missing phase time")?
+            .as_str()
+            .parse()
+            .context("Failed to parse phase time")?;
+
+        self.clock_times.push(clock_time.map(str::to_string));
+        self.uptimes.push(
+            line.decorations
+                .uptime
+                .and_then(|uptime| i64::try_from(uptime.as_micros()).ok()),
+        );
+        self.gc_numbers.push(gc_number);
+        self.phase_names.push(phase_name.to_string());
+        self.phase_times_ms.push(phase_time);
         Ok(())
     }
 
@@ -123,43 +143,93 @@ impl BenchSuiteCollect for BenchSuiteCollectG1Phases {
         _: &bench_suite_types::BenchSuiteRun,
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
         let mut rv = Vec::new();
-        if let Some(df) = self.phases_df {
-            // clock_time is the end-of-pause timestamp shared by all phases in a GC.
-            // Suffix sum of time_ms within each gc_number group gives each phase's
-            // offset from end-of-pause back to its own start.
-            let lf = df
-                .lazy()
-                .with_column(col("clock_time").str().to_datetime(
-                    Some(TimeUnit::Milliseconds),
-                    None,
-                    StrptimeOptions {
-                        format: Some("%Y-%m-%dT%H:%M:%S%.3f%z".into()),
-                        strict: false,
-                        exact: true,
-                        cache: true,
-                    },
-                    lit("raise"),
-                ))
-                .with_column(
-                    (col("clock_time").cast(DataType::Int64)
-                        - col("time_ms")
-                            .reverse()
-                            .cum_sum(false)
-                            .reverse()
-                            .over([col("gc_number")])
-                            .cast(DataType::Int64))
-                    .cast(DataType::Datetime(TimeUnit::Milliseconds, None))
-                    .alias("start_time"),
-                )
-                .with_column(
-                    (col("time_ms") * lit(1000.0))
-                        .cast(DataType::Int64)
-                        .cast(DataType::Duration(TimeUnit::Microseconds))
-                        .alias("time_ms"),
-                )
-                .rename(["time_ms"], ["time_us"], false);
+        if self.log_file.is_some() {
+            let gc_type_col: Vec<String> = self
+                .gc_numbers
+                .iter()
+                .map(|n| self.gc_types.get(n).cloned().unwrap_or_default())
+                .collect();
+            let df = df![
+                "clock_time" => self.clock_times,
+                "uptime" => self.uptimes,
+                "gc_number" => self.gc_numbers,
+                "gc_type" => gc_type_col,
+                "name" => self.phase_names,
+                "time_ms" => self.phase_times_ms,
+            ]
+            .context("Failed to create phases DataFrame")?;
+            let lf = transform_phases(df, &self.options);
             rv.push((Intern::from_static("g1_phases"), lf));
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "g1_phases"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn set_options(&mut self, options: &serde_json::Value) -> anyhow::Result<()> {
+        self.options = LogOptions::deserialize(options)?;
+        Ok(())
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        // LEGACY: drop jvm0.txt once all tests use split files
+        &["gc.javalog", "jvm0.txt"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["g1_phases"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        match table {
+            "g1_phases" => bench_suite_collect_results::derive_schema(&PHASES_SCHEMA, |df| {
+                transform_phases(df, &self.options)
+            }),
+            _ => None,
+        }
+    }
+}
+
+// clock_time is the end-of-pause timestamp shared by all phases in a GC.
+// Suffix sum of time_ms within each gc_number group gives each phase's
+// offset from end-of-pause back to its own start.
+fn transform_phases(df: DataFrame, options: &LogOptions) -> LazyFrame {
+    options
+        .lazy(df)
+        .with_column(col("clock_time").str().to_datetime(
+            Some(TimeUnit::Milliseconds),
+            None,
+            StrptimeOptions {
+                format: Some("%Y-%m-%dT%H:%M:%S%.3f%z".into()),
+                strict: false,
+                exact: true,
+                cache: true,
+            },
+            lit("raise"),
+        ))
+        .with_column(col("uptime").cast(DataType::Duration(TimeUnit::Microseconds)))
+        .with_column(
+            (col("clock_time").cast(DataType::Int64)
+                - col("time_ms")
+                    .reverse()
+                    .cum_sum(false)
+                    .reverse()
+                    .over([col("gc_number")])
+                    .cast(DataType::Int64))
+            .cast(DataType::Datetime(TimeUnit::Milliseconds, None))
+            .alias("start_time"),
+        )
+        .with_column(
+            (col("time_ms") * lit(1000.0))
+                .cast(DataType::Int64)
+                .cast(DataType::Duration(TimeUnit::Microseconds))
+                .alias("time_ms"),
+        )
+        .rename(["time_ms"], ["time_us"], false)
 }
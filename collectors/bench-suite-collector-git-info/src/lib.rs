@@ -51,4 +51,20 @@ impl BenchSuiteCollect for BenchSuiteCollectGitInfo {
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "git_info"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["git_info.csv"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["git_info"]
+    }
 }
@@ -5,18 +5,29 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 use string_intern::Intern;
+use unified_log::LogLine;
 
 static THREAD_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"\[[^\]]*\]\[[^\]]*\]\[[^\]]*\] Thread created tid: ([0-9]*), name:"([^"]*)", thread_type:"([^"]*)""#).unwrap()
+    Regex::new(r#"^Thread created tid: ([0-9]*), name:"([^"]*)", thread_type:"([^"]*)""#).unwrap()
 });
 
-static THREAD_ATTACH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"\[[^\]]*\]\[[^\]]*\]\[[^\]]*\] Thread attached \(tid: ([0-9]+)\s*,").unwrap()
+static THREAD_ATTACH_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^Thread attached \(tid: ([0-9]+)\s*,").unwrap());
+
+static THREADS_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::from_iter(vec![
+        Field::new("pid".into(), DataType::UInt64),
+        Field::new("thread_name".into(), DataType::String),
+        Field::new("thread_type".into(), DataType::String),
+    ])
 });
 
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectJavaThreads {
-    threads_df: Option<DataFrame>,
+    /// The os log the threads were read from
+    log_file: Option<String>,
+    /// Map from tid -> (thread_name, thread_type)
+    thread_map: HashMap<u64, (String, String)>,
 }
 
 impl BenchSuiteCollectJavaThreads {
@@ -27,29 +38,33 @@ impl BenchSuiteCollectJavaThreads {
 }
 
 impl BenchSuiteCollect for BenchSuiteCollectJavaThreads {
-    fn process_file(
+    fn wants_lines(&self, _: &bench_suite_types::BenchSuiteRun, _: &str) -> bool {
+        true
+    }
+
+    fn start_lines(
         &mut self,
         _: &bench_suite_types::BenchSuiteRun,
-        file: &mut dyn bench_suite_collect_results::FileInfoInterface,
+        file_name: &str,
     ) -> anyhow::Result<()> {
-        let name = file.name();
-        if name != "os.javalog"
-            && name != "jvm0.txt" // LEGACY: remove once all tests use split files
-        {
-            return Ok(());
-        }
-
-        if self.threads_df.is_some() {
+        if self.log_file.replace(file_name.to_string()).is_some() {
             return Err(anyhow::anyhow!("Duplicate os log files"));
         }
+        Ok(())
+    }
 
-        let content = file.content_string()?;
-
-        // Map from tid -> (thread_name, thread_type)
-        let mut thread_map: HashMap<u64, (String, String)> = HashMap::new();
+    fn log_tags(&self) -> &'static [&'static str] {
+        &["*"]
+    }
 
-        // Parse "Thread created" lines
-        for cap in THREAD_ID_REGEX.captures_iter(content) {
+    fn process_log_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &LogLine,
+    ) -> anyhow::Result<()> {
+        // "Thread created" lines name the thread, even if it was attached first
+        if let Some(cap) = THREAD_ID_REGEX.captures(line.message) {
             let tid: u64 = cap
                 .get(1)
                 .context("Missing tid")?
@@ -67,11 +82,9 @@ impl BenchSuiteCollect for BenchSuiteCollectJavaThreads {
                 .as_str()
                 .to_string();
 
-            thread_map.insert(tid, (name, thread_type));
-        }
-
-        // Parse "Thread attached" lines - add unknown entries for tids not already seen
-        for cap in THREAD_ATTACH_REGEX.captures_iter(content) {
+            self.thread_map.insert(tid, (name, thread_type));
+        } else if let Some(cap) = THREAD_ATTACH_REGEX.captures(line.message) {
+            // "Thread attached" lines add unknown entries for tids not already seen
             let tid: u64 = cap
                 .get(1)
                 .context("Missing tid")?
@@ -79,30 +92,11 @@ impl BenchSuiteCollect for BenchSuiteCollectJavaThreads {
                 .parse()
                 .context("Failed to parse tid")?;
 
-            thread_map
+            self.thread_map
                 .entry(tid)
                 .or_insert_with(|| ("unknown".to_string(), "unknown".to_string()));
         }
 
-        let mut pids: Vec<u64> = Vec::with_capacity(thread_map.len());
-        let mut names: Vec<String> = Vec::with_capacity(thread_map.len());
-        let mut types: Vec<String> = Vec::with_capacity(thread_map.len());
-
-        for (pid, (name, thread_type)) in thread_map {
-            pids.push(pid);
-            names.push(name);
-            types.push(thread_type);
-        }
-
-        let df = df![
-            "pid" => pids,
-            "thread_name" => names,
-            "thread_type" => types,
-        ]
-        .context("Failed to create threads DataFrame")?;
-
-        self.threads_df = Some(df);
-
         Ok(())
     }
 
@@ -111,9 +105,46 @@ impl BenchSuiteCollect for BenchSuiteCollectJavaThreads {
         _: &bench_suite_types::BenchSuiteRun,
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
         let mut rv = Vec::new();
-        if let Some(df) = self.threads_df {
+        if self.log_file.is_some() {
+            let mut pids: Vec<u64> = Vec::with_capacity(self.thread_map.len());
+            let mut names: Vec<String> = Vec::with_capacity(self.thread_map.len());
+            let mut types: Vec<String> = Vec::with_capacity(self.thread_map.len());
+
+            for (pid, (name, thread_type)) in self.thread_map {
+                pids.push(pid);
+                names.push(name);
+                types.push(thread_type);
+            }
+
+            let df = df![
+                "pid" => pids,
+                "thread_name" => names,
+                "thread_type" => types,
+            ]
+            .context("Failed to create threads DataFrame")?;
             rv.push((Intern::from_static("java_threads"), df.lazy()));
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "java_threads"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        // LEGACY: drop jvm0.txt once all tests use split files
+        &["os.javalog", "jvm0.txt"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["java_threads"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        (table == "java_threads").then(|| THREADS_SCHEMA.clone())
+    }
 }
@@ -81,4 +81,20 @@ impl BenchSuiteCollect for BenchSuiteCollectMarkAbuse {
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "mark_abuse"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["mark_abuse*.csv"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["mark_abuse"]
+    }
 }
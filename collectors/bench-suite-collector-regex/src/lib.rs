@@ -0,0 +1,358 @@
+use anyhow::{Context, bail};
+use bench_suite_collect_results::BenchSuiteCollect;
+use polars::prelude::*;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use string_intern::Intern;
+
+/// Type the text of a capture group is cast to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ColumnType {
+    Str,
+    I64,
+    U64,
+    F64,
+    /// Parsed with the collector's `timestamp_format`
+    Datetime,
+}
+
+impl ColumnType {
+    fn dtype(self) -> DataType {
+        match self {
+            Self::Str => DataType::String,
+            Self::I64 => DataType::Int64,
+            Self::U64 => DataType::UInt64,
+            Self::F64 => DataType::Float64,
+            Self::Datetime => DataType::Datetime(TimeUnit::Microseconds, None),
+        }
+    }
+}
+
+/// A regex collector as written in the `regex_collectors` of a task file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegexCollectorDefinition {
+    /// Name of the table the matches are written to
+    table: String,
+    /// Archive members to read, where a `*` matches any run of characters
+    files: Vec<String>,
+    /// Matched against every line of the files, each named group becomes a column
+    regex: String,
+    /// Types of the columns, `str` for the groups not listed
+    #[serde(default)]
+    types: HashMap<String, ColumnType>,
+    /// strftime format of the `datetime` columns, e.g. `%Y-%m-%dT%H:%M:%S%.3f%z`
+    #[serde(default)]
+    timestamp_format: Option<String>,
+}
+
+/// A table extracted from some files of each run by matching a regex against every line, with
+/// a column per named capture group.
+#[derive(Debug)]
+pub struct RegexCollectorConfig {
+    /// The table name, as the only element of `output_tables`
+    table: &'static [&'static str],
+    files: &'static [&'static str],
+    regex: Regex,
+    /// Capture group names with their types, in the order they appear in the regex
+    columns: Vec<(String, ColumnType)>,
+    timestamp_format: Option<String>,
+}
+
+impl RegexCollectorConfig {
+    /// Parses one of the `regex_collectors` of a task file, e.g.
+    /// `{"table": "heap", "files": ["jvm0.stdout"], "regex": "heap (?<used_mb>\\d+)M", "types": {"used_mb": "u64"}}`.
+    ///
+    /// The table name and file patterns are leaked, as collectors hand them out as `'static`,
+    /// so a config is meant to be parsed once per task and shared by all runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `value` does not describe a regex collector, the regex is invalid or has
+    /// no named groups, `types` names a group the regex does not have, or there are `datetime`
+    /// columns without a `timestamp_format`.
+    pub fn from_value(value: &serde_json::Value) -> anyhow::Result<Arc<Self>> {
+        let definition = RegexCollectorDefinition::deserialize(value)?;
+        let table = definition.table;
+        let regex = Regex::new(&definition.regex)
+            .with_context(|| format!("Invalid regex for table {table}"))?;
+
+        let columns: Vec<(String, ColumnType)> = regex
+            .capture_names()
+            .flatten()
+            .map(|name| {
+                let column_type = definition.types.get(name).copied();
+                (name.to_string(), column_type.unwrap_or(ColumnType::Str))
+            })
+            .collect();
+        if columns.is_empty() {
+            bail!("The regex for table {table} has no named groups");
+        }
+        for name in definition.types.keys() {
+            if !columns.iter().any(|(column, _)| column == name) {
+                bail!("The regex for table {table} has no group named {name}");
+            }
+        }
+        if definition.timestamp_format.is_none()
+            && columns.iter().any(|(_, t)| *t == ColumnType::Datetime)
+        {
+            bail!("Table {table} has datetime columns but no timestamp_format");
+        }
+
+        let table: &'static str = table.leak();
+        let files: Vec<&'static str> = definition
+            .files
+            .into_iter()
+            .map(|file| &*file.leak())
+            .collect();
+        Ok(Arc::new(Self {
+            table: Box::leak(Box::new([table])),
+            files: files.leak(),
+            regex,
+            columns,
+            timestamp_format: definition.timestamp_format,
+        }))
+    }
+
+    /// Name of the table the collector writes.
+    #[must_use]
+    pub fn table(&self) -> &'static str {
+        self.table[0]
+    }
+
+    fn raw_schema(&self) -> Schema {
+        self.columns
+            .iter()
+            .map(|(name, _)| Field::new(name.as_str().into(), DataType::String))
+            .collect()
+    }
+
+    /// Casts the captured text of every column to its type.
+    fn transform(&self, df: DataFrame) -> LazyFrame {
+        self.columns
+            .iter()
+            .fold(df.lazy(), |lf, (name, column_type)| match column_type {
+                ColumnType::Str => lf,
+                ColumnType::Datetime => lf.with_column(col(name.as_str()).str().to_datetime(
+                    Some(TimeUnit::Microseconds),
+                    None,
+                    StrptimeOptions {
+                        format: self.timestamp_format.as_deref().map(Into::into),
+                        strict: true,
+                        exact: true,
+                        cache: true,
+                    },
+                    lit("raise"),
+                )),
+                _ => lf.with_column(col(name.as_str()).strict_cast(column_type.dtype())),
+            })
+    }
+
+    /// The text of every named group, in column order, if `line` matches the regex.
+    fn captures<'l>(&self, line: &'l str) -> Option<Vec<Option<&'l str>>> {
+        let cap = self.regex.captures(line)?;
+        Some(
+            self.columns
+                .iter()
+                .map(|(name, _)| cap.name(name).map(|m| m.as_str()))
+                .collect(),
+        )
+    }
+
+    /// Builds the table from the captured text of each column.
+    fn to_table(&self, values: Vec<Vec<Option<String>>>) -> anyhow::Result<DataFrame> {
+        let height = values.first().map_or(0, Vec::len);
+        let columns = self
+            .columns
+            .iter()
+            .zip(values)
+            .map(|((name, _), values)| Column::new(name.as_str().into(), values))
+            .collect();
+        let df = DataFrame::new(height, columns)
+            .with_context(|| format!("Failed to create {} DataFrame", self.table()))?;
+        // Collected here so text that does not parse as its type is reported with the run
+        self.transform(df)
+            .collect()
+            .with_context(|| format!("Failed to cast the columns of {}", self.table()))
+    }
+}
+
+/// Collects the table of a `RegexCollectorConfig` from the lines its regex matches.
+#[derive(Debug)]
+pub struct BenchSuiteCollectRegex {
+    config: Arc<RegexCollectorConfig>,
+    /// Whether any of the files was read, the table is left out otherwise
+    read_file: bool,
+    /// The text of every group of every match, a column per group
+    values: Vec<Vec<Option<String>>>,
+}
+
+impl BenchSuiteCollectRegex {
+    #[must_use]
+    pub fn boxed(config: Arc<RegexCollectorConfig>) -> Box<dyn BenchSuiteCollect> {
+        let values = vec![Vec::new(); config.columns.len()];
+        Box::new(Self {
+            config,
+            read_file: false,
+            values,
+        })
+    }
+}
+
+impl BenchSuiteCollect for BenchSuiteCollectRegex {
+    fn wants_lines(&self, _: &bench_suite_types::BenchSuiteRun, _: &str) -> bool {
+        true
+    }
+
+    fn start_lines(&mut self, _: &bench_suite_types::BenchSuiteRun, _: &str) -> anyhow::Result<()> {
+        self.read_file = true;
+        Ok(())
+    }
+
+    fn process_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &str,
+    ) -> anyhow::Result<()> {
+        if let Some(captures) = self.config.captures(line) {
+            for (values, value) in self.values.iter_mut().zip(captures) {
+                values.push(value.map(str::to_string));
+            }
+        }
+        Ok(())
+    }
+
+    fn get_result(
+        self: Box<Self>,
+        _: &bench_suite_types::BenchSuiteRun,
+    ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
+        if !self.read_file {
+            return Ok(Vec::new());
+        }
+        let df = self.config.to_table(self.values)?;
+        Ok(vec![(Intern::new(self.config.table()), df.lazy())])
+    }
+
+    fn name(&self) -> &'static str {
+        // Named after the table, so the collectors of a task are told apart in collectors.json
+        self.config.table()
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        self.config.files
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        self.config.table
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        if table != self.config.table() {
+            return None;
+        }
+        bench_suite_collect_results::derive_schema(&self.config.raw_schema(), |df| {
+            self.config.transform(df)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bench_suite_collector_testkit::{RunBuilder, run_on_files};
+
+    #[test]
+    fn named_groups_become_typed_columns() {
+        let config = RegexCollectorConfig::from_value(&serde_json::json!({
+            "table": "heap",
+            "files": ["jvm0.stdout"],
+            "regex": r"^(?<time>\S+) heap (?<used_mb>\d+)M( of (?<total_mb>\d+)M)?",
+            "types": {"time": "datetime", "used_mb": "u64", "total_mb": "u64"},
+            "timestamp_format": "%Y-%m-%dT%H:%M:%S",
+        }))
+        .unwrap();
+        assert_eq!(config.table(), "heap");
+
+        let run = RunBuilder::new("dacapo_samples2").build();
+        let stdout = "\
+2026-07-17T10:50:06 heap 228M of 1024M
+unrelated line
+2026-07-17T10:50:07 heap 34M
+";
+        let tables = run_on_files(
+            BenchSuiteCollectRegex::boxed(Arc::clone(&config)),
+            &run,
+            [
+                ("jvm0.stdout", stdout.as_bytes()),
+                // Not one of the files, so its match is not collected
+                ("jvm0.stderr", b"2026-07-17T10:50:08 heap 1M\n".as_slice()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(tables.len(), 1);
+        let (table, lf) = tables.into_iter().next().unwrap();
+        assert_eq!(table.as_str(), "heap");
+        let df = lf.collect().unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(
+            df.column("used_mb").unwrap().u64().unwrap().get(1),
+            Some(34)
+        );
+        assert_eq!(df.column("total_mb").unwrap().null_count(), 1);
+
+        // Without any of its files the table is left out
+        let tables = run_on_files(
+            BenchSuiteCollectRegex::boxed(Arc::clone(&config)),
+            &run,
+            [("jvm0.stderr", stdout.as_bytes())],
+        )
+        .unwrap();
+        assert!(tables.is_empty());
+
+        let unknown_group = RegexCollectorConfig::from_value(&serde_json::json!({
+            "table": "heap",
+            "files": ["jvm0.stdout"],
+            "regex": r"heap (?<used_mb>\d+)M",
+            "types": {"used": "u64"},
+        }));
+        assert!(unknown_group.is_err());
+    }
+
+    #[test]
+    fn offset_timestamps_are_converted_to_utc() {
+        let config = RegexCollectorConfig::from_value(&serde_json::json!({
+            "table": "gc_start",
+            "files": ["gc.javalog"],
+            "regex": r"^\[(?<time>[^\]]+)\].* GC\((?<gc_number>\d+)\)",
+            "types": {"time": "datetime", "gc_number": "u64"},
+            "timestamp_format": "%Y-%m-%dT%H:%M:%S%.3f%z",
+        }))
+        .unwrap();
+        let collector = BenchSuiteCollectRegex::boxed(Arc::clone(&config));
+        assert_eq!(collector.name(), "gc_start");
+        let schema = collector.output_schema("gc_start").unwrap();
+        // Parsing with %z gives a time zone aware column, which the schema has to say
+        assert!(matches!(
+            schema.get("time"),
+            Some(DataType::Datetime(TimeUnit::Microseconds, Some(_)))
+        ));
+
+        let run = RunBuilder::new("dacapo_samples2").build();
+        let log = b"[2026-07-17T10:50:06.042-0400][info   ][gc          ] GC(0) Major Collection\n";
+        let tables = run_on_files(collector, &run, [("gc.javalog", log.as_slice())]).unwrap();
+        let (_, lf) = tables.into_iter().next().unwrap();
+        let df = lf.collect().unwrap();
+        assert_eq!(**df.schema(), schema);
+        let time = df.column("time").unwrap().datetime().unwrap();
+        // 10:50:06.042 at -04:00 is 14:50:06.042 UTC
+        assert_eq!(time.physical().get(0), Some(1_784_299_806_042_000));
+    }
+}
@@ -188,6 +188,22 @@ impl BenchSuiteCollect for BenchSuiteCollectSpecjbb {
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "specjbb"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["*.raw", "*-Controller.log"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["specjbb_summary", "specjbb_profile"]
+    }
 }
 
 #[cfg(test)]
@@ -2,8 +2,12 @@ use anyhow::Context;
 use bench_suite_collect_results::BenchSuiteCollect;
 use polars::prelude::*;
 use std::collections::HashMap;
+use std::sync::LazyLock;
 use string_intern::Intern;
 
+static STATUS_SCHEMA: LazyLock<Schema> =
+    LazyLock::new(|| Schema::from_iter(vec![Field::new("status".into(), DataType::String)]));
+
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectStatus {
     status: Option<String>,
@@ -34,15 +38,6 @@ impl BenchSuiteCollect for BenchSuiteCollectStatus {
             return Ok(());
         }
 
-        if name == "os.javalog"
-            || name == "jvm0.txt" // LEGACY: remove once all tests use split files
-        {
-            if file.content_string()?.contains("Resctrl: Failed") {
-                self.resctrl_failed = true;
-            }
-            return Ok(());
-        }
-
         // Check for runnerN.exit files
         if let Some(rest) = name.strip_prefix("runner")
             && let Some(num_str) = rest.strip_suffix(".exit")
@@ -59,6 +54,23 @@ impl BenchSuiteCollect for BenchSuiteCollectStatus {
         Ok(())
     }
 
+    fn wants_lines(&self, _: &bench_suite_types::BenchSuiteRun, file_name: &str) -> bool {
+        // LEGACY: drop jvm0.txt once all tests use split files
+        file_name == "os.javalog" || file_name == "jvm0.txt"
+    }
+
+    fn process_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &str,
+    ) -> anyhow::Result<()> {
+        if line.contains("Resctrl: Failed") {
+            self.resctrl_failed = true;
+        }
+        Ok(())
+    }
+
     fn get_result(
         self: Box<Self>,
         _: &bench_suite_types::BenchSuiteRun,
@@ -89,4 +101,24 @@ impl BenchSuiteCollect for BenchSuiteCollectStatus {
 
         Ok(vec![(Intern::from_static("status"), df.lazy())])
     }
+
+    fn name(&self) -> &'static str {
+        "status"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["status.txt", "os.javalog", "jvm0.txt", "runner*.exit"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["status"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        (table == "status").then(|| STATUS_SCHEMA.clone())
+    }
 }
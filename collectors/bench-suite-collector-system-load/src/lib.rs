@@ -1,12 +1,14 @@
 use anyhow::Context;
 use bench_suite_collect_results::BenchSuiteCollect;
 use polars::prelude::*;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 use string_intern::Intern;
 
 #[derive(Default)]
 pub struct BenchSuiteCollectSystemLoad {
+    options: SystemLoadOptions,
     tables: HashMap<Intern, LazyFrame>,
 }
 
@@ -40,16 +42,31 @@ impl BenchSuiteCollectSystemLoad {
     }
 }
 
-static DROP_VALS: LazyLock<Vec<(Intern, Vec<&'static str>)>> = LazyLock::new(|| {
-    vec![
-        (Intern::from_static("cpu_all_cores_sadf"), vec!["%idle"]),
-        (Intern::from_static("cpu_sadf"), vec!["%idle"]),
-        (
-            Intern::from_static("memory_sadf"),
-            vec!["%memused", "%commit"],
-        ),
-    ]
-});
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SystemLoadOptions {
+    /// Columns dropped from each table, e.g. `{"memory_sadf": ["%memused"]}`. Tables not listed
+    /// keep all their columns.
+    drop_columns: HashMap<Intern, Vec<String>>,
+}
+
+impl Default for SystemLoadOptions {
+    fn default() -> Self {
+        Self {
+            drop_columns: HashMap::from([
+                (
+                    Intern::from_static("cpu_all_cores_sadf"),
+                    vec!["%idle".to_string()],
+                ),
+                (Intern::from_static("cpu_sadf"), vec!["%idle".to_string()]),
+                (
+                    Intern::from_static("memory_sadf"),
+                    vec!["%memused".to_string(), "%commit".to_string()],
+                ),
+            ]),
+        }
+    }
+}
 
 static ALL_CPU_NAME: LazyLock<Intern> = LazyLock::new(|| Intern::from_static("cpu_all_cores_sadf"));
 
@@ -110,8 +127,9 @@ impl BenchSuiteCollect for BenchSuiteCollectSystemLoad {
         mut self: Box<Self>,
         _: &bench_suite_types::BenchSuiteRun,
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
-        for (name, cols) in DROP_VALS.iter() {
-            self.drop_cols(*name, cols.iter().copied());
+        let drop_columns = core::mem::take(&mut self.options.drop_columns);
+        for (name, cols) in drop_columns {
+            self.drop_cols(name, cols);
         }
         if let Some(v) = self.tables.get_mut(&ALL_CPU_NAME) {
             let tmp = core::mem::take(v);
@@ -119,4 +137,25 @@ impl BenchSuiteCollect for BenchSuiteCollectSystemLoad {
         }
         Ok(self.tables.into_iter().collect())
     }
+
+    fn name(&self) -> &'static str {
+        "system_load"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn set_options(&mut self, options: &serde_json::Value) -> anyhow::Result<()> {
+        self.options = SystemLoadOptions::deserialize(options)?;
+        Ok(())
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["cpu_data.csv", "*.sadf"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["*_sadf"]
+    }
 }
@@ -1,14 +1,34 @@
 use anyhow::Context;
 use bench_suite_collect_results::BenchSuiteCollect;
 use polars::prelude::*;
+use serde::Deserialize;
 use std::sync::LazyLock;
 use string_intern::Intern;
 
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThreadstatOptions {
+    /// Drop the counts threadstat failed to read, which it writes as -1
+    drop_failed_reads: bool,
+}
+
+impl Default for ThreadstatOptions {
+    fn default() -> Self {
+        Self {
+            drop_failed_reads: true,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct BenchSuiteCollectThreadstat {
+    options: ThreadstatOptions,
     event: Option<LazyFrame>,
     counter_description: Option<LazyFrame>,
     read: Option<LazyFrame>,
+    skip_event: bool,
+    skip_counter_description: bool,
+    skip_read: bool,
 }
 
 impl BenchSuiteCollectThreadstat {
@@ -53,7 +73,7 @@ impl BenchSuiteCollect for BenchSuiteCollectThreadstat {
             return Ok(());
         }
         match file.name() {
-            "threadstat-event.csv" => {
+            "threadstat-event.csv" if !self.skip_event => {
                 if self.event.is_some() {
                     return Err(anyhow::anyhow!("Duplicate threadstat-event.csv files"));
                 }
@@ -70,15 +90,18 @@ impl BenchSuiteCollect for BenchSuiteCollectThreadstat {
                 //The count should be unsigned, but as seen in the schema above it is not
                 //the reason for this is that threadstat outputs -1 when it fails to read the
                 //file descriptor. I believe this would happen when we try to open the perf event after
-                //the process has closed. So right now I drop those columns and cast our column to unsigned
-                let lf = df
-                    .lazy()
-                    .filter(col("count").neq(lit(-1)))
-                    .with_column(col("count").cast(DataType::UInt64));
+                //the process has closed. So unless they are kept I drop those columns and cast our column to unsigned
+                let lf = if self.options.drop_failed_reads {
+                    df.lazy()
+                        .filter(col("count").neq(lit(-1)))
+                        .with_column(col("count").cast(DataType::UInt64))
+                } else {
+                    df.lazy()
+                };
 
                 self.event = Some(lf);
             }
-            "threadstat-event-description.csv" => {
+            "threadstat-event-description.csv" if !self.skip_counter_description => {
                 if self.counter_description.is_some() {
                     return Err(anyhow::anyhow!(
                         "Duplicate threadstat-event-description.csv files"
@@ -96,7 +119,7 @@ impl BenchSuiteCollect for BenchSuiteCollectThreadstat {
 
                 self.counter_description = Some(df.lazy());
             }
-            "threadstat-read.csv" => {
+            "threadstat-read.csv" if !self.skip_read => {
                 if self.read.is_some() {
                     return Err(anyhow::anyhow!("Duplicate threadstat-read.csv files"));
                 }
@@ -140,4 +163,67 @@ impl BenchSuiteCollect for BenchSuiteCollectThreadstat {
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "threadstat"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn set_options(&mut self, options: &serde_json::Value) -> anyhow::Result<()> {
+        self.options = ThreadstatOptions::deserialize(options)?;
+        Ok(())
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["threadstat-*"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &[
+            "threadstat_event",
+            "threadstat_counter_description",
+            "threadstat_read",
+        ]
+    }
+
+    fn retain_tables(&mut self, keep: &dyn Fn(&str) -> bool) {
+        // threadstat-event.csv is by far the largest file in most runs, so not parsing it when
+        // the table is dropped is worth the bookkeeping
+        self.skip_event = !keep("threadstat_event");
+        self.skip_counter_description = !keep("threadstat_counter_description");
+        self.skip_read = !keep("threadstat_read");
+    }
+}
+
+#[cfg(test)]
+mod end_to_end {
+    use super::*;
+    use bench_suite_collector_testkit::{RunBuilder, assert_snapshot, run_on_dir};
+    use std::path::Path;
+
+    #[test]
+    fn drops_failed_reads_unless_kept() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let fixture = dir.join("fixtures/failed_read");
+        let run = RunBuilder::new("dacapo_samples2").build();
+        let tables = run_on_dir(BenchSuiteCollectThreadstat::boxed(), &run, &fixture).unwrap();
+        assert_snapshot(&dir.join("snapshots"), "failed_read", tables);
+
+        let mut collector = BenchSuiteCollectThreadstat::boxed();
+        collector
+            .set_options(&serde_json::json!({"drop_failed_reads": false}))
+            .unwrap();
+        let tables = run_on_dir(collector, &run, &fixture).unwrap();
+        assert_snapshot(&dir.join("snapshots"), "failed_read_kept", tables);
+
+        let mut collector = BenchSuiteCollectThreadstat::boxed();
+        assert!(
+            collector
+                .set_options(&serde_json::json!({"drop_failed_read": false}))
+                .is_err()
+        );
+    }
 }
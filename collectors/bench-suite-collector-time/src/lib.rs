@@ -52,4 +52,20 @@ impl BenchSuiteCollect for BenchSuiteCollectTime {
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "time"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        &["jvm0.time"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["time"]
+    }
 }
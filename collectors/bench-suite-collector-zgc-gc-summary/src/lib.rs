@@ -1,9 +1,12 @@
 use anyhow::Context;
-use bench_suite_collect_results::BenchSuiteCollect;
+use bench_suite_collect_results::{BenchSuiteCollect, LogOptions};
+use bench_suite_types::GcKind;
 use polars::prelude::*;
 use regex::Regex;
+use serde::Deserialize;
 use std::sync::LazyLock;
 use string_intern::Intern;
+use unified_log::{Level, LogLine};
 
 // Matches the completed-collection summary line, e.g.:
 // [2026-07-17T10:50:06.042-0400][info   ][gc          ] GC(0) Major Collection (Metadata GC Threshold) 228M(0%)->34M(0%) 0.042s
@@ -11,14 +14,42 @@ use string_intern::Intern;
 // deliberately not matched by this regex)
 static GC_SUMMARY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r"\[([^\]]*)\]\[info\s*\]\[gc\s*\] GC\((\d+)\) (Major|Minor) Collection \(([^)]+)\) (\d+)M\((\d+)%\)->(\d+)M\((\d+)%\) ([0-9.]+)s",
+        r"^GC\((\d+)\) (Major|Minor) Collection \(([^)]+)\) (\d+)M\((\d+)%\)->(\d+)M\((\d+)%\) ([0-9.]+)s",
     )
     .unwrap()
 });
 
+static SUMMARY_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::from_iter(vec![
+        Field::new("clock_time".into(), DataType::String),
+        Field::new("uptime".into(), DataType::Int64),
+        Field::new("gc_number".into(), DataType::UInt32),
+        Field::new("type".into(), DataType::String),
+        Field::new("cause".into(), DataType::String),
+        Field::new("start_memory_mb".into(), DataType::UInt64),
+        Field::new("start_percent".into(), DataType::UInt8),
+        Field::new("end_memory_mb".into(), DataType::UInt64),
+        Field::new("end_percent".into(), DataType::UInt8),
+        Field::new("time_s".into(), DataType::Float64),
+    ])
+});
+
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectZgcGcSummary {
-    summary_df: Option<DataFrame>,
+    options: LogOptions,
+    /// The gc log the rows were read from
+    log_file: Option<String>,
+    clock_times: Vec<Option<String>>,
+    /// Microseconds since the JVM started, if the log has an uptime decoration
+    uptimes: Vec<Option<i64>>,
+    gc_numbers: Vec<u32>,
+    types: Vec<String>,
+    causes: Vec<String>,
+    start_memory_mb: Vec<u64>,
+    start_percent: Vec<u8>,
+    end_memory_mb: Vec<u64>,
+    end_percent: Vec<u8>,
+    time_s: Vec<f64>,
 }
 
 impl BenchSuiteCollectZgcGcSummary {
@@ -29,106 +60,92 @@ impl BenchSuiteCollectZgcGcSummary {
 }
 
 impl BenchSuiteCollect for BenchSuiteCollectZgcGcSummary {
-    fn process_file(
+    fn wants_lines(&self, run: &bench_suite_types::BenchSuiteRun, _: &str) -> bool {
+        run.gc.is_some_and(|gc| gc.kind() == GcKind::Zgc)
+    }
+
+    fn start_lines(
         &mut self,
-        run: &bench_suite_types::BenchSuiteRun,
-        file: &mut dyn bench_suite_collect_results::FileInfoInterface,
+        _: &bench_suite_types::BenchSuiteRun,
+        file_name: &str,
     ) -> anyhow::Result<()> {
-        // Only process if GC is ZGC
-        match &run.gc {
-            Some(gc) if gc.as_str().to_lowercase().contains("zgc") => {}
-            _ => return Ok(()),
-        }
-
-        let name = file.name();
-        if name != "gc.javalog"
-            && name != "jvm0.txt" // LEGACY: remove once all tests use split files
-        {
-            return Ok(());
-        }
-
-        if self.summary_df.is_some() {
+        if self.log_file.replace(file_name.to_string()).is_some() {
             return Err(anyhow::anyhow!("Duplicate gc log files"));
         }
+        Ok(())
+    }
 
-        let content = file.content_string()?;
-
-        let mut clock_times: Vec<String> = Vec::new();
-        let mut gc_numbers: Vec<u32> = Vec::new();
-        let mut types: Vec<String> = Vec::new();
-        let mut causes: Vec<String> = Vec::new();
-        let mut start_memory_mb: Vec<u64> = Vec::new();
-        let mut start_percent: Vec<u8> = Vec::new();
-        let mut end_memory_mb: Vec<u64> = Vec::new();
-        let mut end_percent: Vec<u8> = Vec::new();
-        let mut time_s: Vec<f64> = Vec::new();
-
-        for cap in GC_SUMMARY_REGEX.captures_iter(content) {
-            let clock_time = cap.get(1).context("Missing clock time")?.as_str();
-            let gc_number: u32 = cap
-                .get(2)
-                .context("Missing GC number")?
-                .as_str()
-                .parse()
-                .context("Failed to parse GC number")?;
-            let gc_type = cap.get(3).context("Missing GC type")?.as_str();
-            let cause = cap.get(4).context("Missing cause")?.as_str();
-            let start_mb: u64 = cap
-                .get(5)
-                .context("Missing start memory")?
-                .as_str()
-                .parse()
-                .context("Failed to parse start memory")?;
-            let start_pct: u8 = cap
-                .get(6)
-                .context("Missing start percent")?
-                .as_str()
-                .parse()
-                .context("Failed to parse start percent")?;
-            let end_mb: u64 = cap
-                .get(7)
-                .context("Missing end memory")?
-                .as_str()
-                .parse()
-                .context("Failed to parse end memory")?;
-            let end_pct: u8 = cap
-                .get(8)
-                .context("Missing end percent")?
-                .as_str()
-                .parse()
-                .context("Failed to parse end percent")?;
-            let time: f64 = cap
-                .get(9)
-                .context("Missing time")?
-                .as_str()
-                .parse()
-                .context("Failed to parse time")?;
-
-            clock_times.push(clock_time.to_string());
-            gc_numbers.push(gc_number);
-            types.push(gc_type.to_lowercase());
-            causes.push(cause.to_string());
-            start_memory_mb.push(start_mb);
-            start_percent.push(start_pct);
-            end_memory_mb.push(end_mb);
-            end_percent.push(end_pct);
-            time_s.push(time);
+    fn log_tags(&self) -> &'static [&'static str] {
+        &["gc"]
+    }
+
+    fn process_log_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &LogLine,
+    ) -> anyhow::Result<()> {
+        if !line.is_level(Level::Info) {
+            return Ok(());
         }
 
-        let df = df![
-            "clock_time" => clock_times,
-            "gc_number" => gc_numbers,
-            "type" => types,
-            "cause" => causes,
-            "start_memory_mb" => start_memory_mb,
-            "start_percent" => start_percent,
-            "end_memory_mb" => end_memory_mb,
-            "end_percent" => end_percent,
-            "time_s" => time_s,
-        ]
-        .context("Failed to create GC summary DataFrame")?;
+        let Some(cap) = GC_SUMMARY_REGEX.captures(line.message) else {
+            return Ok(());
+        };
+        let clock_time = line.decorations.time;
+        let gc_number: u32 = cap
+            .get(1)
+            .context("Missing GC number")?
+            .as_str()
+            .parse()
+            .context("Failed to parse GC number")?;
+        let gc_type = cap.get(2).context("Missing GC type")?.as_str();
+        let cause = cap.get(3).context("Missing cause")?.as_str();
+        let start_mb: u64 = cap
+            .get(4)
+            .context("Missing start memory")?
+            .as_str()
+            .parse()
+            .context("Failed to parse start memory")?;
+        let start_pct: u8 = cap
+            .get(5)
+            .context("Missing start percent")?
+            .as_str()
+            .parse()
+            .context("Failed to parse start percent")?;
+        let end_mb: u64 = cap
+            .get(6)
+            .context("Missing end memory")?
+            .as_str()
+            .parse()
+            .context("Failed to parse end memory")?;
+        let end_pct: u8 = cap
+            .get(7)
+            .context("Missing end percent")?
+            .as_str()
+            .parse()
+            .context("Failed to parse end percent")?;
+        let time: f64 = cap
+            .get(8)
+            .context("Missing time")?
+            .as_str()
+            .parse()
+            .context("Failed to parse time")?;
 
-        self.summary_df = Some(df);
+        self.clock_times.push(clock_time.map(str::to_string));
+        self.uptimes.push(
+            line.decorations
+                .uptime
+                .and_then(|uptime| i64::try_from(uptime.as_micros()).ok()),
+        );
+        self.gc_numbers.push(gc_number);
+        self.types.push(gc_type.to_lowercase());
+        self.causes.push(cause.to_string());
+        self.start_memory_mb.push(start_mb);
+        self.start_percent.push(start_pct);
+        self.end_memory_mb.push(end_mb);
+        self.end_percent.push(end_pct);
+        self.time_s.push(time);
 
         Ok(())
     }
@@ -138,15 +155,63 @@ impl BenchSuiteCollect for BenchSuiteCollectZgcGcSummary {
         _: &bench_suite_types::BenchSuiteRun,
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
         let mut rv = Vec::new();
-        if let Some(df) = self.summary_df {
-            rv.push((Intern::from_static("zgc_gc_summary"), transform_summary(df)));
+        if self.log_file.is_some() {
+            let df = df![
+                "clock_time" => self.clock_times,
+                "uptime" => self.uptimes,
+                "gc_number" => self.gc_numbers,
+                "type" => self.types,
+                "cause" => self.causes,
+                "start_memory_mb" => self.start_memory_mb,
+                "start_percent" => self.start_percent,
+                "end_memory_mb" => self.end_memory_mb,
+                "end_percent" => self.end_percent,
+                "time_s" => self.time_s,
+            ]
+            .context("Failed to create GC summary DataFrame")?;
+            rv.push((
+                Intern::from_static("zgc_gc_summary"),
+                transform_summary(df, &self.options),
+            ));
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "zgc_gc_summary"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn set_options(&mut self, options: &serde_json::Value) -> anyhow::Result<()> {
+        self.options = LogOptions::deserialize(options)?;
+        Ok(())
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        // LEGACY: drop jvm0.txt once all tests use split files
+        &["gc.javalog", "jvm0.txt"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["zgc_gc_summary"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        match table {
+            "zgc_gc_summary" => bench_suite_collect_results::derive_schema(&SUMMARY_SCHEMA, |df| {
+                transform_summary(df, &self.options)
+            }),
+            _ => None,
+        }
+    }
 }
 
-fn transform_summary(df: DataFrame) -> LazyFrame {
-    df.lazy()
+fn transform_summary(df: DataFrame, options: &LogOptions) -> LazyFrame {
+    options
+        .lazy(df)
         .with_column(col("clock_time").str().to_datetime(
             Some(TimeUnit::Milliseconds),
             None,
@@ -158,6 +223,7 @@ fn transform_summary(df: DataFrame) -> LazyFrame {
             },
             lit("raise"),
         ))
+        .with_column(col("uptime").cast(DataType::Duration(TimeUnit::Microseconds)))
         .with_column(
             (col("time_s") * lit(1_000_000.0))
                 .cast(DataType::Int64)
@@ -179,21 +245,25 @@ mod tests {
 [2026-07-17T10:51:25.505-0400][info   ][gc          ] GC(4) Minor Collection (Allocation Rate)
 [2026-07-17T10:51:30.202-0400][info   ][gc          ] GC(4) Minor Collection (Allocation Rate) 49168M(30%)->20970M(13%) 4.697s
 ";
-        let caps: Vec<_> = GC_SUMMARY_REGEX.captures_iter(sample).collect();
+        let caps: Vec<_> = sample
+            .lines()
+            .filter_map(LogLine::parse)
+            .filter_map(|line| GC_SUMMARY_REGEX.captures(line.message))
+            .collect();
         assert_eq!(caps.len(), 2, "start-of-cycle lines must not match");
 
-        assert_eq!(&caps[0][2], "0");
-        assert_eq!(&caps[0][3], "Major");
-        assert_eq!(&caps[0][4], "Metadata GC Threshold");
-        assert_eq!(&caps[0][5], "228");
-        assert_eq!(&caps[0][6], "0");
-        assert_eq!(&caps[0][7], "34");
-        assert_eq!(&caps[0][8], "0");
-        assert_eq!(&caps[0][9], "0.042");
-
-        assert_eq!(&caps[1][3], "Minor");
-        assert_eq!(&caps[1][4], "Allocation Rate");
-        assert_eq!(&caps[1][9], "4.697");
+        assert_eq!(&caps[0][1], "0");
+        assert_eq!(&caps[0][2], "Major");
+        assert_eq!(&caps[0][3], "Metadata GC Threshold");
+        assert_eq!(&caps[0][4], "228");
+        assert_eq!(&caps[0][5], "0");
+        assert_eq!(&caps[0][6], "34");
+        assert_eq!(&caps[0][7], "0");
+        assert_eq!(&caps[0][8], "0.042");
+
+        assert_eq!(&caps[1][2], "Minor");
+        assert_eq!(&caps[1][3], "Allocation Rate");
+        assert_eq!(&caps[1][8], "4.697");
     }
 }
 
@@ -205,6 +275,7 @@ mod schema_check {
     fn end_time_column_is_a_real_datetime() {
         let df = df![
             "clock_time" => ["2026-07-17T10:50:06.042-0400"],
+            "uptime" => [6_042_000i64],
             "gc_number" => [0u32],
             "type" => ["major"],
             "cause" => ["Metadata GC Threshold"],
@@ -216,7 +287,7 @@ mod schema_check {
         ]
         .unwrap();
 
-        let mut lf = transform_summary(df);
+        let mut lf = transform_summary(df, &LogOptions::default());
         let schema = lf.collect_schema().unwrap();
         let dtype = schema.get("end_time").expect("end_time column must exist");
         assert!(
@@ -227,5 +298,31 @@ mod schema_check {
             schema.get("clock_time").is_none(),
             "clock_time should have been renamed to end_time"
         );
+        assert_eq!(
+            schema.get("uptime"),
+            Some(&DataType::Duration(TimeUnit::Microseconds))
+        );
+    }
+}
+
+#[cfg(test)]
+mod end_to_end {
+    use super::*;
+    use bench_suite_collector_testkit::{RunBuilder, assert_snapshot, run_on_dir};
+    use std::path::Path;
+
+    #[test]
+    fn collects_completed_collections_at_info_level() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let run = RunBuilder::new("dacapo_samples2")
+            .field("gc", "ZGC")
+            .build();
+        let tables = run_on_dir(
+            BenchSuiteCollectZgcGcSummary::boxed(),
+            &run,
+            &dir.join("fixtures/major_and_minor"),
+        )
+        .unwrap();
+        assert_snapshot(&dir.join("snapshots"), "major_and_minor", tables);
     }
 }
@@ -1,20 +1,41 @@
 use anyhow::Context;
-use bench_suite_collect_results::BenchSuiteCollect;
+use bench_suite_collect_results::{BenchSuiteCollect, LogOptions};
+use bench_suite_types::GcKind;
 use polars::prelude::*;
 use regex::Regex;
+use serde::Deserialize;
 use std::sync::LazyLock;
 use string_intern::Intern;
+use unified_log::{Level, LogLine};
 
-static GC_PHASE_TIMES_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(
-        r"\[([^\]]*)\]\[info\s*\]\[gc,phases\s*\] GC\((\d+)\) ([YOyo]): ([A-Za-z \-]+) ([0-9.]+)ms",
-    )
-    .unwrap()
+static GC_PHASE_TIMES_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^GC\((\d+)\) ([YOyo]): ([A-Za-z \-]+) ([0-9.]+)ms").unwrap());
+
+static PHASES_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::from_iter(vec![
+        Field::new("clock_time".into(), DataType::String),
+        Field::new("uptime".into(), DataType::Int64),
+        Field::new("gc_number".into(), DataType::UInt32),
+        Field::new("age".into(), DataType::String),
+        Field::new("type".into(), DataType::String),
+        Field::new("name".into(), DataType::String),
+        Field::new("time_ms".into(), DataType::Float64),
+    ])
 });
 
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectZgcPhases {
-    phases_df: Option<DataFrame>,
+    options: LogOptions,
+    /// The gc log the rows were read from
+    log_file: Option<String>,
+    clock_times: Vec<Option<String>>,
+    /// Microseconds since the JVM started, if the log has an uptime decoration
+    uptimes: Vec<Option<i64>>,
+    gc_numbers: Vec<u32>,
+    phase_ages: Vec<String>,
+    phase_types: Vec<String>,
+    phase_names: Vec<String>,
+    phase_times_ms: Vec<f64>,
 }
 
 impl BenchSuiteCollectZgcPhases {
@@ -25,84 +46,77 @@ impl BenchSuiteCollectZgcPhases {
 }
 
 impl BenchSuiteCollect for BenchSuiteCollectZgcPhases {
-    fn process_file(
+    fn wants_lines(&self, run: &bench_suite_types::BenchSuiteRun, _: &str) -> bool {
+        run.gc.is_some_and(|gc| gc.kind() == GcKind::Zgc)
+    }
+
+    fn start_lines(
         &mut self,
-        run: &bench_suite_types::BenchSuiteRun,
-        file: &mut dyn bench_suite_collect_results::FileInfoInterface,
+        _: &bench_suite_types::BenchSuiteRun,
+        file_name: &str,
     ) -> anyhow::Result<()> {
-        // Only process if GC is ZGC
-        match &run.gc {
-            Some(gc) if gc.as_str().to_lowercase().contains("zgc") => {}
-            _ => return Ok(()),
+        if self.log_file.replace(file_name.to_string()).is_some() {
+            return Err(anyhow::anyhow!("Duplicate gc log files"));
         }
+        Ok(())
+    }
 
-        let name = file.name();
-        if name != "gc.javalog"
-            && name != "jvm0.txt" // LEGACY: remove once all tests use split files
-        {
-            return Ok(());
-        }
+    fn log_tags(&self) -> &'static [&'static str] {
+        &["gc,phases"]
+    }
 
-        if self.phases_df.is_some() {
-            return Err(anyhow::anyhow!("Duplicate gc log files"));
+    fn process_log_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &LogLine,
+    ) -> anyhow::Result<()> {
+        if !line.is_level(Level::Info) {
+            return Ok(());
         }
 
-        let content = file.content_string()?;
-
-        let mut clock_times: Vec<String> = Vec::new();
-        let mut gc_numbers: Vec<u32> = Vec::new();
-        let mut phase_ages: Vec<String> = Vec::new();
-        let mut phase_types: Vec<String> = Vec::new();
-        let mut phase_names: Vec<String> = Vec::new();
-        let mut phase_times_ms: Vec<f64> = Vec::new();
-
-        for cap in GC_PHASE_TIMES_REGEX.captures_iter(content) {
-            let clock_time = cap.get(1).context("Missing clock time")?.as_str();
-            let gc_number: u32 = cap
-                .get(2)
-                .context("Missing GC number")?
-                .as_str()
-                .parse()
-                .context("Failed to parse GC number")?;
-            let phase_type = cap.get(3).context("Missing phase type")?.as_str();
-            let phase_name = cap.get(4).context("Missing phase name")?.as_str().trim();
-            let phase_time: f64 = cap
-                .get(5)
-                .context("Missing phase time")?
-                .as_str()
-                .parse()
-                .context("Failed to parse phase time")?;
-
-            clock_times.push(clock_time.to_string());
-            gc_numbers.push(gc_number);
-            let type_char = phase_type
-                .chars()
-                .next()
-                .ok_or(anyhow::anyhow!("ZGC age does not have a single char"))?;
-            phase_types.push(
-                if type_char.is_lowercase() {
-                    "minor"
-                } else {
-                    "major"
-                }
-                .into(),
-            );
-            phase_ages.push(type_char.to_lowercase().to_string());
-            phase_names.push(phase_name.to_string());
-            phase_times_ms.push(phase_time);
-        }
+        let Some(cap) = GC_PHASE_TIMES_REGEX.captures(line.message) else {
+            return Ok(());
+        };
+        let clock_time = line.decorations.time;
+        let gc_number: u32 = cap
+            .get(1)
+            .context("Missing GC number")?
+            .as_str()
+            .parse()
+            .context("Failed to parse GC number")?;
+        let phase_type = cap.get(2).context("Missing phase type")?.as_str();
+        let phase_name = cap.get(3).context("Missing phase name")?.as_str().trim();
+        let phase_time: f64 = cap
+            .get(4)
+            .context("Missing phase time")?
+            .as_str()
+            .parse()
+            .context("Failed to parse phase time")?;
 
-        let df = df![
-            "clock_time" => clock_times,
-            "gc_number" => gc_numbers,
-            "age" => phase_ages,
-            "type"=>phase_types,
-            "name" => phase_names,
-            "time_ms" => phase_times_ms,
-        ]
-        .context("Failed to create phases DataFrame")?;
+        let type_char = phase_type
+            .chars()
+            .next()
+            .ok_or(anyhow::anyhow!("ZGC age does not have a single char"))?;
 
-        self.phases_df = Some(df);
+        self.clock_times.push(clock_time.map(str::to_string));
+        self.uptimes.push(
+            line.decorations
+                .uptime
+                .and_then(|uptime| i64::try_from(uptime.as_micros()).ok()),
+        );
+        self.gc_numbers.push(gc_number);
+        self.phase_types.push(
+            if type_char.is_lowercase() {
+                "minor"
+            } else {
+                "major"
+            }
+            .into(),
+        );
+        self.phase_ages.push(type_char.to_lowercase().to_string());
+        self.phase_names.push(phase_name.to_string());
+        self.phase_times_ms.push(phase_time);
 
         Ok(())
     }
@@ -112,35 +126,114 @@ impl BenchSuiteCollect for BenchSuiteCollectZgcPhases {
         _: &bench_suite_types::BenchSuiteRun,
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
         let mut rv = Vec::new();
-        if let Some(df) = self.phases_df {
-            let lf = df
-                .lazy()
-                .with_column(col("clock_time").str().to_datetime(
-                    Some(TimeUnit::Milliseconds),
-                    None,
-                    StrptimeOptions {
-                        format: Some("%Y-%m-%dT%H:%M:%S%.3f%z".into()),
-                        strict: false,
-                        exact: true,
-                        cache: true,
-                    },
-                    lit("raise"),
-                ))
-                .with_column(
-                    (col("clock_time").cast(DataType::Int64)
-                        - col("time_ms").cast(DataType::Int64))
-                    .cast(DataType::Datetime(TimeUnit::Milliseconds, None))
-                    .alias("start_time"),
-                )
-                .with_column(
-                    (col("time_ms") * lit(1000.0))
-                        .cast(DataType::Int64)
-                        .cast(DataType::Duration(TimeUnit::Microseconds))
-                        .alias("time_ms"),
-                )
-                .rename(["time_ms"], ["time_us"], false);
+        if self.log_file.is_some() {
+            let df = df![
+                "clock_time" => self.clock_times,
+                "uptime" => self.uptimes,
+                "gc_number" => self.gc_numbers,
+                "age" => self.phase_ages,
+                "type" => self.phase_types,
+                "name" => self.phase_names,
+                "time_ms" => self.phase_times_ms,
+            ]
+            .context("Failed to create phases DataFrame")?;
+            let lf = transform_phases(df, &self.options);
             rv.push((Intern::from_static("zgc_phases"), lf));
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "zgc_phases"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn set_options(&mut self, options: &serde_json::Value) -> anyhow::Result<()> {
+        self.options = LogOptions::deserialize(options)?;
+        Ok(())
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        // LEGACY: drop jvm0.txt once all tests use split files
+        &["gc.javalog", "jvm0.txt"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["zgc_phases"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        match table {
+            "zgc_phases" => bench_suite_collect_results::derive_schema(&PHASES_SCHEMA, |df| {
+                transform_phases(df, &self.options)
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn transform_phases(df: DataFrame, options: &LogOptions) -> LazyFrame {
+    options
+        .lazy(df)
+        .with_column(col("clock_time").str().to_datetime(
+            Some(TimeUnit::Milliseconds),
+            None,
+            StrptimeOptions {
+                format: Some("%Y-%m-%dT%H:%M:%S%.3f%z".into()),
+                strict: false,
+                exact: true,
+                cache: true,
+            },
+            lit("raise"),
+        ))
+        .with_column(col("uptime").cast(DataType::Duration(TimeUnit::Microseconds)))
+        .with_column(
+            (col("clock_time").cast(DataType::Int64) - col("time_ms").cast(DataType::Int64))
+                .cast(DataType::Datetime(TimeUnit::Milliseconds, None))
+                .alias("start_time"),
+        )
+        .with_column(
+            (col("time_ms") * lit(1000.0))
+                .cast(DataType::Int64)
+                .cast(DataType::Duration(TimeUnit::Microseconds))
+                .alias("time_ms"),
+        )
+        .rename(["time_ms"], ["time_us"], false)
+}
+
+#[cfg(test)]
+mod end_to_end {
+    use super::*;
+    use bench_suite_collector_testkit::{RunBuilder, assert_snapshot, run_on_dir};
+    use std::path::Path;
+
+    #[test]
+    fn collects_young_and_old_phases() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let fixture = dir.join("fixtures/young_and_old");
+        let run = RunBuilder::new("dacapo_samples2")
+            .field("gc", "ZGC")
+            .build();
+        let tables = run_on_dir(BenchSuiteCollectZgcPhases::boxed(), &run, &fixture).unwrap();
+        assert_snapshot(&dir.join("snapshots"), "young_and_old", tables);
+
+        let mut collector = BenchSuiteCollectZgcPhases::boxed();
+        collector
+            .set_options(&serde_json::json!({"keep_raw_clock_time": true}))
+            .unwrap();
+        let tables = run_on_dir(collector, &run, &fixture).unwrap();
+        assert_snapshot(
+            &dir.join("snapshots"),
+            "young_and_old_raw_clock_time",
+            tables,
+        );
+
+        // Only ZGC logs have these phases
+        let run = RunBuilder::new("dacapo_samples2").field("gc", "G1").build();
+        let tables = run_on_dir(BenchSuiteCollectZgcPhases::boxed(), &run, &fixture).unwrap();
+        assert!(tables.is_empty());
+    }
 }
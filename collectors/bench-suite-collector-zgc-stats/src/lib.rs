@@ -1,23 +1,60 @@
 use anyhow::Context;
-use bench_suite_collect_results::BenchSuiteCollect;
+use bench_suite_collect_results::{BenchSuiteCollect, LogOptions};
+use bench_suite_types::GcKind;
 use polars::prelude::*;
 use regex::Regex;
+use serde::Deserialize;
 use std::sync::LazyLock;
 use string_intern::Intern;
+use unified_log::{Level, LogLine};
 
 // Matches stat lines like:
 //   [timestamp][info][gc,stats    ]        Contention: Mark Segment Reset Contention                    18 / 87               24 / 182              24 / 182              24 / 182         ops/s
 //   [timestamp][info][gc,stats    ]          Critical: Allocation Stall                              0.000 / 0.000         3.589 / 14.601        3.589 / 14.601        3.589 / 14.601      ms
 static ZGC_STATS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(
-        r"\[([^\]]*)\]\[info\s*\]\[gc,stats\s*\]\s+([\w ]+): (.+?)\s{2,}(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\S+)",
+        r"^\s*([\w ]+): (.+?)\s{2,}(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\d+(?:\.\d+)?) / (\d+(?:\.\d+)?)\s+(\S+)",
     )
     .unwrap()
 });
 
+static STATS_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::from_iter(vec![
+        Field::new("clock_time".into(), DataType::String),
+        Field::new("uptime".into(), DataType::Int64),
+        Field::new("stat_type".into(), DataType::String),
+        Field::new("name".into(), DataType::String),
+        Field::new("unit".into(), DataType::String),
+        Field::new("last_10s_avg".into(), DataType::Float64),
+        Field::new("last_10s_max".into(), DataType::Float64),
+        Field::new("last_10m_avg".into(), DataType::Float64),
+        Field::new("last_10m_max".into(), DataType::Float64),
+        Field::new("last_10h_avg".into(), DataType::Float64),
+        Field::new("last_10h_max".into(), DataType::Float64),
+        Field::new("total_avg".into(), DataType::Float64),
+        Field::new("total_max".into(), DataType::Float64),
+    ])
+});
+
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectZgcStats {
-    stats_df: Option<DataFrame>,
+    options: LogOptions,
+    /// The gc log the rows were read from
+    log_file: Option<String>,
+    clock_times: Vec<Option<String>>,
+    /// Microseconds since the JVM started, if the log has an uptime decoration
+    uptimes: Vec<Option<i64>>,
+    stat_types: Vec<String>,
+    names: Vec<String>,
+    last_10s_avg: Vec<f64>,
+    last_10s_max: Vec<f64>,
+    last_10m_avg: Vec<f64>,
+    last_10m_max: Vec<f64>,
+    last_10h_avg: Vec<f64>,
+    last_10h_max: Vec<f64>,
+    total_avg: Vec<f64>,
+    total_max: Vec<f64>,
+    units: Vec<String>,
 }
 
 impl BenchSuiteCollectZgcStats {
@@ -28,127 +65,108 @@ impl BenchSuiteCollectZgcStats {
 }
 
 impl BenchSuiteCollect for BenchSuiteCollectZgcStats {
-    fn process_file(
+    fn wants_lines(&self, run: &bench_suite_types::BenchSuiteRun, _: &str) -> bool {
+        run.gc.is_some_and(|gc| gc.kind() == GcKind::Zgc)
+    }
+
+    fn start_lines(
         &mut self,
-        run: &bench_suite_types::BenchSuiteRun,
-        file: &mut dyn bench_suite_collect_results::FileInfoInterface,
+        _: &bench_suite_types::BenchSuiteRun,
+        file_name: &str,
     ) -> anyhow::Result<()> {
-        match &run.gc {
-            Some(gc) if gc.as_str().to_lowercase().contains("zgc") => {}
-            _ => return Ok(()),
+        if self.log_file.replace(file_name.to_string()).is_some() {
+            return Err(anyhow::anyhow!("Duplicate gc log files"));
         }
+        Ok(())
+    }
 
-        let name = file.name();
-        if name != "gc.javalog"
-            && name != "jvm0.txt" // LEGACY: remove once all tests use split files
-        {
-            return Ok(());
-        }
+    fn log_tags(&self) -> &'static [&'static str] {
+        &["gc,stats"]
+    }
 
-        if self.stats_df.is_some() {
-            return Err(anyhow::anyhow!("Duplicate gc log files"));
+    fn process_log_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &LogLine,
+    ) -> anyhow::Result<()> {
+        if !line.is_level(Level::Info) {
+            return Ok(());
         }
 
-        let content = file.content_string()?;
-
-        let mut clock_times: Vec<String> = Vec::new();
-        let mut stat_types: Vec<String> = Vec::new();
-        let mut names: Vec<String> = Vec::new();
-        let mut last_10s_avg: Vec<f64> = Vec::new();
-        let mut last_10s_max: Vec<f64> = Vec::new();
-        let mut last_10m_avg: Vec<f64> = Vec::new();
-        let mut last_10m_max: Vec<f64> = Vec::new();
-        let mut last_10h_avg: Vec<f64> = Vec::new();
-        let mut last_10h_max: Vec<f64> = Vec::new();
-        let mut total_avg: Vec<f64> = Vec::new();
-        let mut total_max: Vec<f64> = Vec::new();
-        let mut units: Vec<String> = Vec::new();
-
-        for cap in ZGC_STATS_REGEX.captures_iter(content) {
-            let clock_time = cap.get(1).context("Missing clock time")?.as_str();
-            let stat_type = cap.get(2).context("Missing stat type")?.as_str().trim();
-            let name = cap.get(3).context("Missing name")?.as_str().trim();
-            let l10s_avg: f64 = cap
-                .get(4)
-                .context("Missing last_10s_avg")?
-                .as_str()
-                .parse()
-                .context("Failed to parse last_10s_avg")?;
-            let l10s_max: f64 = cap
-                .get(5)
-                .context("Missing last_10s_max")?
-                .as_str()
-                .parse()
-                .context("Failed to parse last_10s_max")?;
-            let l10m_avg: f64 = cap
-                .get(6)
-                .context("Missing last_10m_avg")?
-                .as_str()
-                .parse()
-                .context("Failed to parse last_10m_avg")?;
-            let l10m_max: f64 = cap
-                .get(7)
-                .context("Missing last_10m_max")?
-                .as_str()
-                .parse()
-                .context("Failed to parse last_10m_max")?;
-            let l10h_avg: f64 = cap
-                .get(8)
-                .context("Missing last_10h_avg")?
-                .as_str()
-                .parse()
-                .context("Failed to parse last_10h_avg")?;
-            let l10h_max: f64 = cap
-                .get(9)
-                .context("Missing last_10h_max")?
-                .as_str()
-                .parse()
-                .context("Failed to parse last_10h_max")?;
-            let tot_avg: f64 = cap
-                .get(10)
-                .context("Missing total_avg")?
-                .as_str()
-                .parse()
-                .context("Failed to parse total_avg")?;
-            let tot_max: f64 = cap
-                .get(11)
-                .context("Missing total_max")?
-                .as_str()
-                .parse()
-                .context("Failed to parse total_max")?;
-            let unit = cap.get(12).context("Missing unit")?.as_str();
-
-            clock_times.push(clock_time.to_string());
-            stat_types.push(stat_type.to_string());
-            names.push(name.to_string());
-            last_10s_avg.push(l10s_avg);
-            last_10s_max.push(l10s_max);
-            last_10m_avg.push(l10m_avg);
-            last_10m_max.push(l10m_max);
-            last_10h_avg.push(l10h_avg);
-            last_10h_max.push(l10h_max);
-            total_avg.push(tot_avg);
-            total_max.push(tot_max);
-            units.push(unit.to_string());
-        }
+        let Some(cap) = ZGC_STATS_REGEX.captures(line.message) else {
+            return Ok(());
+        };
+        let clock_time = line.decorations.time;
+        let stat_type = cap.get(1).context("Missing stat type")?.as_str().trim();
+        let name = cap.get(2).context("Missing name")?.as_str().trim();
+        let l10s_avg: f64 = cap
+            .get(3)
+            .context("Missing last_10s_avg")?
+            .as_str()
+            .parse()
+            .context("Failed to parse last_10s_avg")?;
+        let l10s_max: f64 = cap
+            .get(4)
+            .context("Missing last_10s_max")?
+            .as_str()
+            .parse()
+            .context("Failed to parse last_10s_max")?;
+        let l10m_avg: f64 = cap
+            .get(5)
+            .context("Missing last_10m_avg")?
+            .as_str()
+            .parse()
+            .context("Failed to parse last_10m_avg")?;
+        let l10m_max: f64 = cap
+            .get(6)
+            .context("Missing last_10m_max")?
+            .as_str()
+            .parse()
+            .context("Failed to parse last_10m_max")?;
+        let l10h_avg: f64 = cap
+            .get(7)
+            .context("Missing last_10h_avg")?
+            .as_str()
+            .parse()
+            .context("Failed to parse last_10h_avg")?;
+        let l10h_max: f64 = cap
+            .get(8)
+            .context("Missing last_10h_max")?
+            .as_str()
+            .parse()
+            .context("Failed to parse last_10h_max")?;
+        let tot_avg: f64 = cap
+            .get(9)
+            .context("Missing total_avg")?
+            .as_str()
+            .parse()
+            .context("Failed to parse total_avg")?;
+        let tot_max: f64 = cap
+            .get(10)
+            .context("Missing total_max")?
+            .as_str()
+            .parse()
+            .context("Failed to parse total_max")?;
+        let unit = cap.get(11).context("Missing unit")?.as_str();
 
-        let df = df![
-            "clock_time" => clock_times,
-            "stat_type" => stat_types,
-            "name" => names,
-            "unit" => units,
-            "last_10s_avg" => last_10s_avg,
-            "last_10s_max" => last_10s_max,
-            "last_10m_avg" => last_10m_avg,
-            "last_10m_max" => last_10m_max,
-            "last_10h_avg" => last_10h_avg,
-            "last_10h_max" => last_10h_max,
-            "total_avg" => total_avg,
-            "total_max" => total_max,
-        ]
-        .context("Failed to create zgc_stats DataFrame")?;
-
-        self.stats_df = Some(df);
+        self.clock_times.push(clock_time.map(str::to_string));
+        self.uptimes.push(
+            line.decorations
+                .uptime
+                .and_then(|uptime| i64::try_from(uptime.as_micros()).ok()),
+        );
+        self.stat_types.push(stat_type.to_string());
+        self.names.push(name.to_string());
+        self.last_10s_avg.push(l10s_avg);
+        self.last_10s_max.push(l10s_max);
+        self.last_10m_avg.push(l10m_avg);
+        self.last_10m_max.push(l10m_max);
+        self.last_10h_avg.push(l10h_avg);
+        self.last_10h_max.push(l10h_max);
+        self.total_avg.push(tot_avg);
+        self.total_max.push(tot_max);
+        self.units.push(unit.to_string());
 
         Ok(())
     }
@@ -158,20 +176,74 @@ impl BenchSuiteCollect for BenchSuiteCollectZgcStats {
         _: &bench_suite_types::BenchSuiteRun,
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
         let mut rv = Vec::new();
-        if let Some(df) = self.stats_df {
-            let lf = df.lazy().with_column(col("clock_time").str().to_datetime(
-                Some(TimeUnit::Milliseconds),
-                None,
-                StrptimeOptions {
-                    format: Some("%Y-%m-%dT%H:%M:%S%.3f%z".into()),
-                    strict: false,
-                    exact: true,
-                    cache: true,
-                },
-                lit("raise"),
-            ));
+        if self.log_file.is_some() {
+            let df = df![
+                "clock_time" => self.clock_times,
+                "uptime" => self.uptimes,
+                "stat_type" => self.stat_types,
+                "name" => self.names,
+                "unit" => self.units,
+                "last_10s_avg" => self.last_10s_avg,
+                "last_10s_max" => self.last_10s_max,
+                "last_10m_avg" => self.last_10m_avg,
+                "last_10m_max" => self.last_10m_max,
+                "last_10h_avg" => self.last_10h_avg,
+                "last_10h_max" => self.last_10h_max,
+                "total_avg" => self.total_avg,
+                "total_max" => self.total_max,
+            ]
+            .context("Failed to create zgc_stats DataFrame")?;
+            let lf = transform_stats(df, &self.options);
             rv.push((Intern::from_static("zgc_stats"), lf));
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "zgc_stats"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn set_options(&mut self, options: &serde_json::Value) -> anyhow::Result<()> {
+        self.options = LogOptions::deserialize(options)?;
+        Ok(())
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        // LEGACY: drop jvm0.txt once all tests use split files
+        &["gc.javalog", "jvm0.txt"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["zgc_stats"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        match table {
+            "zgc_stats" => bench_suite_collect_results::derive_schema(&STATS_SCHEMA, |df| {
+                transform_stats(df, &self.options)
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn transform_stats(df: DataFrame, options: &LogOptions) -> LazyFrame {
+    options
+        .lazy(df)
+        .with_column(col("clock_time").str().to_datetime(
+            Some(TimeUnit::Milliseconds),
+            None,
+            StrptimeOptions {
+                format: Some("%Y-%m-%dT%H:%M:%S%.3f%z".into()),
+                strict: false,
+                exact: true,
+                cache: true,
+            },
+            lit("raise"),
+        ))
+        .with_column(col("uptime").cast(DataType::Duration(TimeUnit::Microseconds)))
 }
@@ -1,20 +1,33 @@
 use anyhow::Context;
 use bench_suite_collect_results::BenchSuiteCollect;
+use bench_suite_types::GcKind;
 use polars::prelude::*;
 use regex::Regex;
 use std::sync::LazyLock;
 use string_intern::Intern;
+use unified_log::LogLine;
 
 // Matches:
 //   GC(759) Using 2 Workers for Young Generation
 //   GC(759) Using 1 Workers for Old Generation
-static GC_TASK_WORKERS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"\[gc,task\s*\] GC\((\d+)\) Using (\d+) Workers for (\w+) Generation").unwrap()
+static GC_TASK_WORKERS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^GC\((\d+)\) Using (\d+) Workers for (\w+) Generation").unwrap());
+
+static TASK_WORKERS_SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
+    Schema::from_iter(vec![
+        Field::new("gc_number".into(), DataType::UInt64),
+        Field::new("age".into(), DataType::String),
+        Field::new("num_workers".into(), DataType::UInt32),
+    ])
 });
 
 #[derive(Debug, Default)]
 pub struct BenchSuiteCollectZgcTask {
-    task_df: Option<DataFrame>,
+    /// The gc log the rows were read from
+    log_file: Option<String>,
+    gc_numbers: Vec<u64>,
+    ages: Vec<&'static str>,
+    num_workers: Vec<u32>,
 }
 
 impl BenchSuiteCollectZgcTask {
@@ -25,71 +38,61 @@ impl BenchSuiteCollectZgcTask {
 }
 
 impl BenchSuiteCollect for BenchSuiteCollectZgcTask {
-    fn process_file(
+    fn wants_lines(&self, run: &bench_suite_types::BenchSuiteRun, _: &str) -> bool {
+        run.gc.is_some_and(|gc| gc.kind() == GcKind::Zgc)
+    }
+
+    fn start_lines(
         &mut self,
-        run: &bench_suite_types::BenchSuiteRun,
-        file: &mut dyn bench_suite_collect_results::FileInfoInterface,
+        _: &bench_suite_types::BenchSuiteRun,
+        file_name: &str,
     ) -> anyhow::Result<()> {
-        match &run.gc {
-            Some(gc) if gc.as_str().to_lowercase().contains("zgc") => {}
-            _ => return Ok(()),
-        }
-
-        let name = file.name();
-        if name != "gc.javalog"
-            && name != "jvm0.txt" // LEGACY: remove once all tests use split files
-        {
-            return Ok(());
-        }
-
-        if self.task_df.is_some() {
+        if self.log_file.replace(file_name.to_string()).is_some() {
             return Err(anyhow::anyhow!("Duplicate gc log files"));
         }
+        Ok(())
+    }
 
-        let content = file.content_string()?;
-
-        let mut gc_numbers: Vec<u64> = Vec::new();
-        let mut ages: Vec<&str> = Vec::new();
-        let mut num_workers: Vec<u32> = Vec::new();
-
-        for cap in GC_TASK_WORKERS_REGEX.captures_iter(content) {
-            let gc_number: u64 = cap
-                .get(1)
-                .context("Missing GC number")?
-                .as_str()
-                .parse()
-                .context("Failed to parse GC number")?;
-            let workers: u32 = cap
-                .get(2)
-                .context("Missing worker count")?
-                .as_str()
-                .parse()
-                .context("Failed to parse worker count")?;
-            let age = if cap
-                .get(3)
-                .context("Missing generation")?
-                .as_str()
-                .to_lowercase()
-                == "young"
-            {
-                "y"
-            } else {
-                "o"
-            };
-
-            gc_numbers.push(gc_number);
-            ages.push(age);
-            num_workers.push(workers);
-        }
+    fn log_tags(&self) -> &'static [&'static str] {
+        &["gc,task"]
+    }
 
-        let df = df![
-            "gc_number" => gc_numbers,
-            "age" => ages,
-            "num_workers" => num_workers,
-        ]
-        .context("Failed to create zgc_task DataFrame")?;
+    fn process_log_line(
+        &mut self,
+        _: &bench_suite_types::BenchSuiteRun,
+        _: &str,
+        line: &LogLine,
+    ) -> anyhow::Result<()> {
+        let Some(cap) = GC_TASK_WORKERS_REGEX.captures(line.message) else {
+            return Ok(());
+        };
+        let gc_number: u64 = cap
+            .get(1)
+            .context("Missing GC number")?
+            .as_str()
+            .parse()
+            .context("Failed to parse GC number")?;
+        let workers: u32 = cap
+            .get(2)
+            .context("Missing worker count")?
+            .as_str()
+            .parse()
+            .context("Failed to parse worker count")?;
+        let age = if cap
+            .get(3)
+            .context("Missing generation")?
+            .as_str()
+            .to_lowercase()
+            == "young"
+        {
+            "y"
+        } else {
+            "o"
+        };
 
-        self.task_df = Some(df);
+        self.gc_numbers.push(gc_number);
+        self.ages.push(age);
+        self.num_workers.push(workers);
 
         Ok(())
     }
@@ -99,9 +102,36 @@ impl BenchSuiteCollect for BenchSuiteCollectZgcTask {
         _: &bench_suite_types::BenchSuiteRun,
     ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
         let mut rv = Vec::new();
-        if let Some(df) = self.task_df {
+        if self.log_file.is_some() {
+            let df = df![
+                "gc_number" => self.gc_numbers,
+                "age" => self.ages,
+                "num_workers" => self.num_workers,
+            ]
+            .context("Failed to create zgc_task DataFrame")?;
             rv.push((Intern::from_static("zgc_task_workers"), df.lazy()));
         }
         Ok(rv)
     }
+
+    fn name(&self) -> &'static str {
+        "zgc_task"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn input_patterns(&self) -> &'static [&'static str] {
+        // LEGACY: drop jvm0.txt once all tests use split files
+        &["gc.javalog", "jvm0.txt"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["zgc_task_workers"]
+    }
+
+    fn output_schema(&self, table: &str) -> Option<Schema> {
+        (table == "zgc_task_workers").then(|| TASK_WORKERS_SCHEMA.clone())
+    }
 }
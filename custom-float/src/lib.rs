@@ -1,7 +1,7 @@
 use core::cmp;
 use core::error;
 use core::fmt::Display;
-use serde::{Deserialize, Deserializer, de::Error as DeError};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as DeError};
 use std::fmt::Debug;
 use std::num::NonZeroU64;
 
@@ -109,3 +109,12 @@ impl<'de> Deserialize<'de> for PositiveNonZeroF64 {
         Self::new(value).map_err(DeError::custom)
     }
 }
+
+impl Serialize for PositiveNonZeroF64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.get())
+    }
+}
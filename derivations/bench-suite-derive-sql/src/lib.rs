@@ -0,0 +1,141 @@
+use anyhow::Context;
+use bench_suite_collect_results::BenchSuiteDerive;
+use polars::prelude::*;
+use polars::sql::SQLContext;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use string_intern::Intern;
+
+/// A SQL query as written in the `sql` of a task file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SqlDefinition {
+    query: String,
+    /// The tables the query reads, which are collected even where they are dropped
+    tables: Vec<String>,
+}
+
+/// A table computed by a SQL query over the other tables of each run.
+#[derive(Debug)]
+pub struct SqlQuery {
+    /// The table name, as the only element of `output_tables`
+    table: &'static [&'static str],
+    tables: &'static [&'static str],
+    query: String,
+}
+
+impl SqlQuery {
+    /// Parses the query of `table` in the `sql` of a task file, e.g.
+    /// `{"query": "SELECT gc_number, SUM(time_ms) AS pause_ms FROM zgc_phases GROUP BY gc_number", "tables": ["zgc_phases"]}`.
+    ///
+    /// The table names are leaked, as derivations hand them out as `'static`, so a query is
+    /// meant to be parsed once per task and shared by all runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `value` does not describe a SQL query.
+    pub fn from_value(table: &str, value: &serde_json::Value) -> anyhow::Result<Arc<Self>> {
+        let definition = SqlDefinition::deserialize(value)
+            .with_context(|| format!("Invalid sql query for {table}"))?;
+        let table: &'static str = table.to_string().leak();
+        let tables: Vec<&'static str> = definition.tables.into_iter().map(|t| &*t.leak()).collect();
+        Ok(Arc::new(Self {
+            table: Box::leak(Box::new([table])),
+            tables: tables.leak(),
+            query: definition.query,
+        }))
+    }
+
+    /// Name of the table the query computes.
+    #[must_use]
+    pub fn table(&self) -> &'static str {
+        self.table[0]
+    }
+
+    fn execute(&self, tables: &HashMap<Intern, LazyFrame>) -> anyhow::Result<LazyFrame> {
+        let mut context = SQLContext::new();
+        for table in self.tables {
+            let lf = tables
+                .get(&Intern::new(*table))
+                .with_context(|| format!("Missing {table}"))?;
+            context.register(table, lf.clone());
+        }
+        context
+            .execute(&self.query)
+            .with_context(|| format!("Failed to run the sql query for {}", self.table()))
+    }
+}
+
+/// Runs a `SqlQuery` against each run's tables.
+#[derive(Debug)]
+pub struct BenchSuiteDeriveSql {
+    query: Arc<SqlQuery>,
+}
+
+impl BenchSuiteDeriveSql {
+    #[must_use]
+    pub fn boxed(query: Arc<SqlQuery>) -> Box<dyn BenchSuiteDerive> {
+        Box::new(Self { query })
+    }
+}
+
+impl BenchSuiteDerive for BenchSuiteDeriveSql {
+    fn name(&self) -> &'static str {
+        "sql"
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        self.query.tables
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        self.query.table
+    }
+
+    fn derive(
+        &self,
+        _: &bench_suite_types::BenchSuiteRun,
+        tables: &HashMap<Intern, LazyFrame>,
+    ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
+        let lf = self.query.execute(tables)?;
+        Ok(vec![(Intern::new(self.query.table()), lf)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_the_tables_it_lists() {
+        let query = SqlQuery::from_value(
+            "gc_pause_totals",
+            &serde_json::json!({
+                "query": "SELECT gc_number, SUM(time_ms) AS pause_ms FROM zgc_phases \
+                          GROUP BY gc_number ORDER BY gc_number",
+                "tables": ["zgc_phases"],
+            }),
+        )
+        .unwrap();
+        assert_eq!(query.table(), "gc_pause_totals");
+
+        let phases = df![
+            "gc_number" => [0u32, 0, 1],
+            "time_ms" => [0.5f64, 1.5, 3.0],
+        ]
+        .unwrap();
+        let tables = HashMap::from([(Intern::from_static("zgc_phases"), phases.lazy())]);
+        let df = query.execute(&tables).unwrap().collect().unwrap();
+        let totals: Vec<Option<f64>> = df
+            .column("pause_ms")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(totals, [Some(2.0), Some(3.0)]);
+
+        assert!(query.execute(&HashMap::new()).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::Context;
+use bench_suite_collect_results::BenchSuiteDerive;
+use polars::prelude::*;
+use std::collections::HashMap;
+use string_intern::Intern;
+
+/// Names the JVM thread each threadstat counter was attached to, by joining
+/// `threadstat_counter_description.pid` onto the thread ids in `java_threads`.
+#[derive(Debug, Default)]
+pub struct BenchSuiteDeriveThreadstatThreads;
+
+impl BenchSuiteDeriveThreadstatThreads {
+    #[must_use]
+    pub fn boxed() -> Box<dyn BenchSuiteDerive> {
+        Box::new(Self)
+    }
+}
+
+impl BenchSuiteDerive for BenchSuiteDeriveThreadstatThreads {
+    fn name(&self) -> &'static str {
+        "threadstat_threads"
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["threadstat_counter_description", "java_threads"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["threadstat_counter_threads"]
+    }
+
+    fn derive(
+        &self,
+        _: &bench_suite_types::BenchSuiteRun,
+        tables: &HashMap<Intern, LazyFrame>,
+    ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
+        let descriptions = tables
+            .get(&Intern::from_static("threadstat_counter_description"))
+            .context("Missing threadstat_counter_description")?
+            .clone();
+        // java_threads calls the thread id a pid too, but reads it as a wider integer
+        let threads = tables
+            .get(&Intern::from_static("java_threads"))
+            .context("Missing java_threads")?
+            .clone()
+            .with_column(col("pid").cast(DataType::UInt32));
+
+        let lf = descriptions.join(
+            threads,
+            [col("pid")],
+            [col("pid")],
+            JoinArgs::new(JoinType::Left),
+        );
+        Ok(vec![(
+            Intern::from_static("threadstat_counter_threads"),
+            lf,
+        )])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bench_suite_collector_testkit::RunBuilder;
+
+    #[test]
+    fn names_the_threads_of_counters() {
+        let descriptions = df![
+            "event_id" => [0u64, 1, 2],
+            "name" => ["cycles", "cycles", "cycles"],
+            "pid" => [101u32, 102, 103],
+        ]
+        .unwrap();
+        let threads = df![
+            "pid" => [101u64, 102],
+            "thread_name" => ["main", "ZWorker#0"],
+            "thread_type" => ["java", "gc"],
+        ]
+        .unwrap();
+        let tables = HashMap::from([
+            (
+                Intern::from_static("threadstat_counter_description"),
+                descriptions.lazy(),
+            ),
+            (Intern::from_static("java_threads"), threads.lazy()),
+        ]);
+        let run = RunBuilder::new("dacapo_samples2").build();
+        let derived = BenchSuiteDeriveThreadstatThreads
+            .derive(&run, &tables)
+            .unwrap();
+        assert_eq!(derived.len(), 1);
+        let (table, lf) = derived.into_iter().next().unwrap();
+        assert_eq!(table.as_str(), "threadstat_counter_threads");
+
+        let df = lf
+            .sort(["event_id"], SortMultipleOptions::default())
+            .collect()
+            .unwrap();
+        let names: Vec<Option<&str>> = df
+            .column("thread_name")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect();
+        // The thread of a counter can exit before java_threads lists the threads
+        assert_eq!(names, [Some("main"), Some("ZWorker#0"), None]);
+    }
+}
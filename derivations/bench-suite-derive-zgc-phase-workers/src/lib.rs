@@ -0,0 +1,105 @@
+use anyhow::Context;
+use bench_suite_collect_results::BenchSuiteDerive;
+use polars::prelude::*;
+use std::collections::HashMap;
+use string_intern::Intern;
+
+/// Labels every ZGC phase with the number of workers its generation's collection used, by
+/// joining `zgc_task_workers` onto `zgc_phases` on the GC number and age.
+#[derive(Debug, Default)]
+pub struct BenchSuiteDeriveZgcPhaseWorkers;
+
+impl BenchSuiteDeriveZgcPhaseWorkers {
+    #[must_use]
+    pub fn boxed() -> Box<dyn BenchSuiteDerive> {
+        Box::new(Self)
+    }
+}
+
+impl BenchSuiteDerive for BenchSuiteDeriveZgcPhaseWorkers {
+    fn name(&self) -> &'static str {
+        "zgc_phase_workers"
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["zgc_phases", "zgc_task_workers"]
+    }
+
+    fn output_tables(&self) -> &'static [&'static str] {
+        &["zgc_phase_workers"]
+    }
+
+    fn derive(
+        &self,
+        _: &bench_suite_types::BenchSuiteRun,
+        tables: &HashMap<Intern, LazyFrame>,
+    ) -> anyhow::Result<Vec<(Intern, LazyFrame)>> {
+        let phases = tables
+            .get(&Intern::from_static("zgc_phases"))
+            .context("Missing zgc_phases")?
+            .clone();
+        let workers = tables
+            .get(&Intern::from_static("zgc_task_workers"))
+            .context("Missing zgc_task_workers")?
+            .clone()
+            .with_column(col("gc_number").cast(DataType::UInt32));
+
+        let lf = phases.join(
+            workers,
+            [col("gc_number"), col("age")],
+            [col("gc_number"), col("age")],
+            JoinArgs::new(JoinType::Left),
+        );
+        Ok(vec![(Intern::from_static("zgc_phase_workers"), lf)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bench_suite_collector_testkit::RunBuilder;
+
+    #[test]
+    fn labels_phases_with_the_workers_of_their_generation() {
+        let phases = df![
+            "gc_number" => [0u32, 0, 0, 1],
+            "age" => ["y", "y", "o", "y"],
+            "name" => ["Mark Start", "Mark End", "Mark Start", "Mark Start"],
+        ]
+        .unwrap();
+        // zgc_task reads the GC number as a wider integer than zgc_phases
+        let workers = df![
+            "gc_number" => [0u64, 0],
+            "age" => ["y", "o"],
+            "num_workers" => [2u32, 1],
+        ]
+        .unwrap();
+        let tables = HashMap::from([
+            (Intern::from_static("zgc_phases"), phases.lazy()),
+            (Intern::from_static("zgc_task_workers"), workers.lazy()),
+        ]);
+        let run = RunBuilder::new("dacapo_samples2")
+            .field("gc", "ZGC")
+            .build();
+        let derived = BenchSuiteDeriveZgcPhaseWorkers
+            .derive(&run, &tables)
+            .unwrap();
+        assert_eq!(derived.len(), 1);
+        let (table, lf) = derived.into_iter().next().unwrap();
+        assert_eq!(table.as_str(), "zgc_phase_workers");
+
+        let df = lf
+            .sort(["gc_number", "age", "name"], SortMultipleOptions::default())
+            .collect()
+            .unwrap();
+        let num_workers: Vec<Option<u32>> = df
+            .column("num_workers")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_iter()
+            .collect();
+        // GC 1 has no task workers line, so its phase is kept without a count
+        assert_eq!(num_workers, [Some(1), Some(2), Some(2), None]);
+    }
+}
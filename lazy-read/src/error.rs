@@ -3,6 +3,7 @@ pub enum Error {
     IO(std::io::Error),
     FromUTF8(std::string::FromUtf8Error),
     UTF8(std::str::Utf8Error),
+    Streamed,
 }
 
 impl std::error::Error for Error {}
@@ -13,6 +14,7 @@ impl std::fmt::Display for Error {
             Error::IO(i) => write!(f, "IO Error: {i}"),
             Error::FromUTF8(i) => write!(f, "UTF8 Error: {i}"),
             Error::UTF8(i) => write!(f, "UTF8 Error: {i}"),
+            Error::Streamed => write!(f, "The content was already read line by line"),
         }
     }
 }
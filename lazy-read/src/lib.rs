@@ -1,6 +1,6 @@
 pub mod error;
-use error::Result;
-use std::io::Read;
+use error::{Error, Result};
+use std::io::{BufRead, BufReader, Read};
 use std::mem;
 pub enum LazyRead<T>
 where
@@ -9,6 +9,8 @@ where
     Orig(T),
     String(String),
     Bytes(Vec<u8>),
+    /// The content was read line by line without being kept, so it cannot be read again.
+    Streamed,
 }
 
 impl<T> LazyRead<T>
@@ -36,6 +38,7 @@ where
                 *self = LazyRead::String(String::from_utf8(tmp)?);
             }
             LazyRead::String(_) => {}
+            LazyRead::Streamed => return Err(Error::Streamed),
         }
 
         Ok(if let LazyRead::String(s) = self {
@@ -56,10 +59,53 @@ where
             *self = LazyRead::Bytes(b);
         }
 
-        Ok(match self {
-            LazyRead::Bytes(b) => b,
-            LazyRead::String(s) => s.as_bytes(),
+        match self {
+            LazyRead::Bytes(b) => Ok(b),
+            LazyRead::String(s) => Ok(s.as_bytes()),
+            LazyRead::Streamed => Err(Error::Streamed),
             LazyRead::Orig(_) => unreachable!(),
-        })
+        }
+    }
+    /// Calls `f` with every line of the content, without its line ending.
+    ///
+    /// Content that was not read yet is streamed through a small buffer instead of being read
+    /// into memory, after which it cannot be read again.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error `f` returns.
+    /// Returns `Error::IO` if reading from the underlying reader fails or a line is not UTF-8.
+    /// Returns `Error::FromUTF8` or `Error::UTF8` if cached content is not valid UTF-8.
+    /// Returns `Error::Streamed` if the content was already streamed.
+    pub fn for_each_line<E>(
+        &mut self,
+        mut f: impl FnMut(&str) -> std::result::Result<(), E>,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<Error>,
+    {
+        match self {
+            LazyRead::Orig(_) => {
+                let LazyRead::Orig(v) = mem::replace(self, LazyRead::Streamed) else {
+                    unreachable!()
+                };
+                let mut reader = BufReader::new(v);
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line).map_err(Error::from)? == 0 {
+                        return Ok(());
+                    }
+                    let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+                    f(trimmed.strip_suffix('\r').unwrap_or(trimmed))?;
+                }
+            }
+            LazyRead::String(s) => s.lines().try_for_each(f),
+            LazyRead::Bytes(b) => std::str::from_utf8(b)
+                .map_err(Error::from)?
+                .lines()
+                .try_for_each(f),
+            LazyRead::Streamed => Err(Error::Streamed.into()),
+        }
     }
 }
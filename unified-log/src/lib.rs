@@ -0,0 +1,210 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// `timemillis` and `uptimemillis` only differ in magnitude, as do `timenanos` and
+/// `uptimenanos`. Anything over about three years is taken to be a time since the epoch.
+const EPOCH_THRESHOLD: Duration = Duration::from_secs(100_000_000);
+
+/// Level of a unified logging line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl FromStr for Level {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trace" => Ok(Self::Trace),
+            "debug" => Ok(Self::Debug),
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// The bracketed decorations in front of a unified logging message, with their padding removed.
+///
+/// Any set of decorators is recognized from the decorations' formats. The JVM prints them in a
+/// fixed order whatever order `-Xlog` lists them in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Decorations<'a> {
+    /// Wall clock time from `time` or `utctime`, e.g. `2026-07-17T10:50:06.042-0400`
+    pub time: Option<&'a str>,
+    /// Wall clock time from `timemillis` or `timenanos`
+    pub time_since_epoch: Option<Duration>,
+    /// Time since the JVM started from `uptime`, `uptimemillis` or `uptimenanos`
+    pub uptime: Option<Duration>,
+    pub pid: Option<u64>,
+    pub tid: Option<u64>,
+    pub level: Option<Level>,
+    /// Comma separated tag set, e.g. `gc,phases`
+    pub tags: Option<&'a str>,
+}
+
+/// A line of a JVM unified logging file, split into its decorations and message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogLine<'a> {
+    pub decorations: Decorations<'a>,
+    pub message: &'a str,
+}
+
+impl<'a> LogLine<'a> {
+    /// Splits `line` into its decorations and message, or returns `None` if it does not start with
+    /// a decoration, such as the continuation lines of multi-line messages.
+    #[must_use]
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let mut decorations = Decorations::default();
+        let mut rest = line.strip_prefix('[')?;
+        loop {
+            let (decoration, after) = rest.split_once(']')?;
+            decorations.add(decoration.trim());
+            match after.strip_prefix('[') {
+                Some(next) => rest = next,
+                None => {
+                    return Some(Self {
+                        decorations,
+                        message: after.strip_prefix(' ').unwrap_or(after),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Whether the line was logged with exactly the tag set `tags`, e.g. `gc,phases`. Lines
+    /// without a `tags` decoration could have been logged with any tags.
+    #[must_use]
+    pub fn has_tags(&self, tags: &str) -> bool {
+        self.decorations.tags.is_none_or(|t| t == tags)
+    }
+
+    /// Whether the line was logged at `level`. Lines without a `level` decoration could have
+    /// been logged at any level.
+    #[must_use]
+    pub fn is_level(&self, level: Level) -> bool {
+        self.decorations.level.is_none_or(|l| l == level)
+    }
+}
+
+impl<'a> Decorations<'a> {
+    fn add(&mut self, decoration: &'a str) {
+        if let Ok(level) = decoration.parse() {
+            self.level = Some(level);
+        } else if let Some(uptime) = parse_uptime(decoration) {
+            self.uptime = Some(uptime);
+        } else if let Some(duration) = parse_integer_duration(decoration) {
+            if duration >= EPOCH_THRESHOLD {
+                self.time_since_epoch = Some(duration);
+            } else {
+                self.uptime = Some(duration);
+            }
+        } else if let Ok(id) = decoration.parse() {
+            // pid is printed before tid, but a lone id is taken to be the tid, as the pid is
+            // the same on every line and so rarely logged on its own
+            self.pid = self.tid.replace(id);
+        } else if decoration
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b',' || b == b'_')
+        {
+            // The hostname comes before the tags, which are always last
+            self.tags = Some(decoration);
+        } else if self.time.is_none() {
+            self.time = Some(decoration);
+        }
+    }
+}
+
+/// Parses an `uptime` decoration like `6.042s`.
+fn parse_uptime(decoration: &str) -> Option<Duration> {
+    let secs: f64 = decoration.strip_suffix('s')?.parse().ok()?;
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Parses a `*millis` or `*nanos` decoration like `6042ms` or `6042000000ns`.
+fn parse_integer_duration(decoration: &str) -> Option<Duration> {
+    if let Some(millis) = decoration.strip_suffix("ms") {
+        millis.parse().ok().map(Duration::from_millis)
+    } else {
+        decoration
+            .strip_suffix("ns")?
+            .parse()
+            .ok()
+            .map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_default_decorations() {
+        let line = LogLine::parse(
+            "[2026-07-17T10:50:06.042-0400][info   ][gc,phases   ] GC(0) Y: Pause Mark Start 0.012ms",
+        )
+        .unwrap();
+        assert_eq!(line.decorations.time, Some("2026-07-17T10:50:06.042-0400"));
+        assert_eq!(line.decorations.uptime, None);
+        assert!(line.is_level(Level::Info));
+        assert!(line.has_tags("gc,phases"));
+        assert!(!line.has_tags("gc"));
+        assert_eq!(line.message, "GC(0) Y: Pause Mark Start 0.012ms");
+
+        let line = LogLine::parse("[1.234s][warning][gc] To-space exhausted").unwrap();
+        assert_eq!(line.decorations.uptime, Some(Duration::from_millis(1234)));
+        assert!(line.is_level(Level::Warning));
+        assert_eq!(line.message, "To-space exhausted");
+
+        assert_eq!(LogLine::parse("   continuation of a message"), None);
+    }
+
+    #[test]
+    fn recognizes_any_decorator_set() {
+        let line = LogLine::parse("[6.042s][31337] GC(0) Major Collection").unwrap();
+        assert_eq!(line.decorations.uptime, Some(Duration::from_millis(6042)));
+        assert_eq!(line.decorations.tid, Some(31337));
+        assert_eq!(line.decorations.pid, None);
+        assert!(line.has_tags("gc") && line.is_level(Level::Info));
+
+        let line = LogLine::parse(
+            "[2026-07-17T14:50:06.042+0000][1784299806042ms][6042000000ns][4242][31337][debug][gc,task] GC(0) Using 2 Workers for Young Generation",
+        )
+        .unwrap();
+        assert_eq!(line.decorations.time, Some("2026-07-17T14:50:06.042+0000"));
+        assert_eq!(
+            line.decorations.time_since_epoch,
+            Some(Duration::from_millis(1_784_299_806_042))
+        );
+        assert_eq!(
+            line.decorations.uptime,
+            Some(Duration::from_secs(6) + Duration::from_millis(42))
+        );
+        assert_eq!(
+            (line.decorations.pid, line.decorations.tid),
+            (Some(4242), Some(31337))
+        );
+        assert!(line.is_level(Level::Debug) && !line.is_level(Level::Info));
+        assert!(line.has_tags("gc,task"));
+        assert_eq!(line.message, "GC(0) Using 2 Workers for Young Generation");
+    }
+}